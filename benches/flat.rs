@@ -0,0 +1,68 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Time the hand-unrolled `flat` zips against the generic recursive zip over the same iterators.
+//! Run with `cargo bench --bench flat`.
+
+use breadth_first_zip::{
+    flat::{BreadthFirstZip2, BreadthFirstZip3},
+    BreadthFirstZip,
+};
+use std::{hint::black_box, time::Instant};
+
+/// Elements in each iterator of the pair.
+const PAIR: u32 = 1_000;
+
+/// Elements in each iterator of the triple.
+const TRIPLE: u32 = 100;
+
+fn main() {
+    let start = Instant::now();
+    let generic = (0..PAIR, 0..PAIR).breadth_first();
+    let mut count = 0_usize;
+    while let Some(combination) = generic.next() {
+        let _ = black_box(combination);
+        count += 1;
+    }
+    let generic_pair = start.elapsed();
+
+    let start = Instant::now();
+    let flat = BreadthFirstZip2::new(0..PAIR, 0..PAIR);
+    let mut flat_count = 0_usize;
+    while let Some(combination) = flat.next() {
+        let _ = black_box(combination);
+        flat_count += 1;
+    }
+    let flat_pair = start.elapsed();
+
+    assert_eq!(count, flat_count, "Both zips should yield every pair");
+    println!("{count} pairs:");
+    println!("  generic: {generic_pair:?}");
+    println!("  flat:    {flat_pair:?}");
+
+    let start = Instant::now();
+    let generic = (0..TRIPLE, 0..TRIPLE, 0..TRIPLE).breadth_first();
+    let mut count = 0_usize;
+    while let Some(combination) = generic.next() {
+        let _ = black_box(combination);
+        count += 1;
+    }
+    let generic_triple = start.elapsed();
+
+    let start = Instant::now();
+    let flat = BreadthFirstZip3::new(0..TRIPLE, 0..TRIPLE, 0..TRIPLE);
+    let mut flat_count = 0_usize;
+    while let Some(combination) = flat.next() {
+        let _ = black_box(combination);
+        flat_count += 1;
+    }
+    let flat_triple = start.elapsed();
+
+    assert_eq!(count, flat_count, "Both zips should yield every triple");
+    println!("{count} triples:");
+    println!("  generic: {generic_triple:?}");
+    println!("  flat:    {flat_triple:?}");
+}
@@ -0,0 +1,66 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Time the `max_index_sum` short-circuit: once the short iterators run out, the tuple zip stops exploring them
+//! for index sums they can't reach, while the array zip (same order, no short-circuit) keeps trying.
+//! Run with `cargo bench --bench max_index_sum`.
+
+use breadth_first_zip::{uniform::BreadthFirstArrayZip, BreadthFirstZip};
+use std::{hint::black_box, time::Instant};
+
+/// Elements in the one long iterator.
+const LONG: u16 = 300;
+
+/// Elements in each of the seven short iterators.
+const SHORT: u16 = 3;
+
+fn main() {
+    let start = Instant::now();
+    let tuple = (
+        0..LONG,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+    )
+        .breadth_first();
+    let mut count = 0_usize;
+    while let Some(combination) = tuple.next() {
+        let _ = black_box(combination);
+        count += 1;
+    }
+    let short_circuited = start.elapsed();
+
+    let start = Instant::now();
+    let array = [
+        0..LONG,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+        0..SHORT,
+    ]
+    .breadth_first();
+    let mut array_count = 0_usize;
+    while let Some(combination) = array.next() {
+        let _ = black_box(combination);
+        array_count += 1;
+    }
+    let exhaustive = start.elapsed();
+
+    assert_eq!(
+        count, array_count,
+        "Both zips should yield every combination"
+    );
+    println!("{count} combinations of 8 iterators:");
+    println!("  tuple zip (short-circuits with `max_index_sum`): {short_circuited:?}");
+    println!("  array zip (explores every iterator every time):  {exhaustive:?}");
+}
@@ -29,6 +29,69 @@ pub fn implement(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .into()
 }
 
+#[proc_macro]
+pub fn implement_array(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    array_fallible(ts.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro]
+pub fn implement_component(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    component_fallible(ts.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro]
+pub fn implement_map_axis(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    map_axis_fallible(ts.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro]
+pub fn implement_split_prefix(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    split_prefix_fallible(ts.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro]
+pub fn implement_pad(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    pad_fallible(ts.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro]
+pub fn implement_merge_axes(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    merge_axes_fallible(ts.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro]
+pub fn implement_zip_n(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    zip_n_fallible(ts.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro]
+pub fn implement_owning(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    owning_fallible(ts.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro]
+pub fn implement_roundtrip_tests(ts: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    roundtrip_tests_fallible(ts.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 #[inline]
 fn flatten_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
     if !ts.is_empty() {
@@ -63,6 +126,376 @@ fn flatten_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
     Ok(out)
 }
 
+#[inline]
+fn array_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
+    if !ts.is_empty() {
+        return Err(syn::Error::new(ts.span(), "This macro takes no arguments"));
+    }
+    let mut out = TokenStream::new();
+    for endc in START_CHAR..=END_CHAR {
+        let chars = START_CHAR..=endc;
+        let len = proc_macro2::Literal::usize_unsuffixed(chars.len());
+        let nested = chars.clone().fold(quote!(()), |acc, _| quote!((T, #acc)));
+        let pattern = chars.clone().rfold(quote!(()), |acc, ref c| {
+            let ident = cr2i(&(c + TO_LOWERCASE));
+            quote!((#ident, #acc))
+        });
+        let elems = chars.map(|ref c| cr2i(&(c + TO_LOWERCASE)));
+        quote! {
+            impl<T> IntoArray for #nested {
+                type Array = [T; #len];
+                #[inline(always)]
+                fn into_array(self) -> Self::Array {
+                    let #pattern = self;
+                    [#(#elems),*]
+                }
+            }
+        }
+        .to_tokens(&mut out);
+    }
+    Ok(out)
+}
+
+#[inline]
+fn component_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
+    if !ts.is_empty() {
+        return Err(syn::Error::new(ts.span(), "This macro takes no arguments"));
+    }
+    let mut out = TokenStream::new();
+    for endc in START_CHAR..=END_CHAR {
+        let chars = START_CHAR..=endc;
+        let index = proc_macro2::Literal::usize_unsuffixed(chars.len() - 1);
+        let types: Vec<_> = chars.clone().map(|ref c| cr2i(c)).collect();
+        let output = cr2i(&endc);
+        let nested = types
+            .iter()
+            .rfold(quote!(Tail), |acc, ty| quote!((#ty, #acc)));
+        let pattern = chars.rfold(quote!(_), |acc, c| {
+            if c == endc {
+                quote!((component, _))
+            } else {
+                quote!((_, #acc))
+            }
+        });
+        quote! {
+            impl<#(#types,)* Tail: sealed::Nested> Component<#index> for #nested {
+                type Output = #output;
+                #[inline(always)]
+                fn component(self) -> Self::Output {
+                    let #pattern = self;
+                    component
+                }
+            }
+        }
+        .to_tokens(&mut out);
+    }
+    Ok(out)
+}
+
+#[inline]
+fn split_prefix_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
+    if !ts.is_empty() {
+        return Err(syn::Error::new(ts.span(), "This macro takes no arguments"));
+    }
+    let mut out = TokenStream::new();
+    for endc in START_CHAR..=END_CHAR {
+        let chars = START_CHAR..=endc;
+        let index = proc_macro2::Literal::usize_unsuffixed(chars.len());
+        let types: Vec<_> = chars.clone().map(|ref c| cr2i(c)).collect();
+        let elems: Vec<_> = chars.map(|ref c| cr2i(&(c + TO_LOWERCASE))).collect();
+        let nested = types
+            .iter()
+            .rfold(quote!(Tail), |acc, ty| quote!((#ty, #acc)));
+        let prefix = types
+            .iter()
+            .rfold(quote!(()), |acc, ty| quote!((#ty, #acc)));
+        let pattern = elems
+            .iter()
+            .rfold(quote!(tail), |acc, elem| quote!((#elem, #acc)));
+        let rebuilt = elems
+            .iter()
+            .rfold(quote!(()), |acc, elem| quote!((#elem, #acc)));
+        quote! {
+            impl<#(#types,)* Tail: sealed::Nested> SplitPrefix<#index> for #nested {
+                type Prefix = #prefix;
+                type Suffix = Tail;
+                #[inline(always)]
+                fn split_prefix(self) -> (Self::Prefix, Self::Suffix) {
+                    let #pattern = self;
+                    (#rebuilt, tail)
+                }
+            }
+        }
+        .to_tokens(&mut out);
+    }
+    Ok(out)
+}
+
+#[inline]
+fn map_axis_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
+    if !ts.is_empty() {
+        return Err(syn::Error::new(ts.span(), "This macro takes no arguments"));
+    }
+    let mut out = TokenStream::new();
+    for endc in START_CHAR..=END_CHAR {
+        let chars = START_CHAR..=endc;
+        let index = proc_macro2::Literal::usize_unsuffixed(chars.len() - 1);
+        let types: Vec<_> = chars.clone().map(|ref c| cr2i(c)).collect();
+        let elems: Vec<_> = chars
+            .clone()
+            .map(|ref c| cr2i(&(c + TO_LOWERCASE)))
+            .collect();
+        let last = cr2i(&endc);
+        let nested = types
+            .iter()
+            .rfold(quote!(Tail), |acc, ty| quote!((#ty, #acc)));
+        let mapped = types.iter().rfold(quote!(Tail), |acc, ty| {
+            if *ty == last {
+                quote!((::core::iter::Map<#ty, Function>, #acc))
+            } else {
+                quote!((#ty, #acc))
+            }
+        });
+        let pattern = elems
+            .iter()
+            .rfold(quote!(tail), |acc, elem| quote!((#elem, #acc)));
+        let last_elem = cr2i(&(endc + TO_LOWERCASE));
+        let rebuilt = elems.iter().rfold(quote!(tail), |acc, elem| {
+            if *elem == last_elem {
+                quote!((#elem.map(function), #acc))
+            } else {
+                quote!((#elem, #acc))
+            }
+        });
+        quote! {
+            impl<#(#types,)* Tail: sealed::Nested, Function: FnMut(#last::Item) -> Output, Output> MapAxis<#index, Function> for #nested
+            where
+                #last: Iterator,
+            {
+                type Mapped = #mapped;
+                #[inline(always)]
+                fn map_axis(self, function: Function) -> Self::Mapped {
+                    let #pattern = self;
+                    #rebuilt
+                }
+            }
+        }
+        .to_tokens(&mut out);
+    }
+    Ok(out)
+}
+
+#[inline]
+fn pad_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
+    if !ts.is_empty() {
+        return Err(syn::Error::new(ts.span(), "This macro takes no arguments"));
+    }
+    let mut out = TokenStream::new();
+    for endc in START_CHAR..=END_CHAR {
+        let chars = START_CHAR..=endc;
+        let types: Vec<_> = chars.clone().map(|ref c| cr2i(c)).collect();
+        let elems: Vec<_> = chars
+            .clone()
+            .map(|ref c| cr2i(&(c + TO_LOWERCASE)))
+            .collect();
+        let defaults: Vec<_> = chars
+            .map(|c| {
+                syn::Ident::new(
+                    &format!("default_{}", cr2s(&(c + TO_LOWERCASE))),
+                    Span::call_site(),
+                )
+            })
+            .collect();
+        quote! {
+            impl<#(#types: ExactSizeIterator,)*> Pad for (#(#types,)*)
+            where
+                #(#types::Item: Clone,)*
+            {
+                type Defaults = (#(#types::Item,)*);
+                type Padded = (#(::core::iter::Chain<#types, ::core::iter::RepeatN<#types::Item>>,)*);
+                #[inline(always)]
+                fn pad(self, defaults: Self::Defaults) -> Self::Padded {
+                    let (#(#elems,)*) = self;
+                    let (#(#defaults,)*) = defaults;
+                    let longest = 0_usize #(.max(#elems.len()))*;
+                    (#({
+                        let missing = longest.saturating_sub(#elems.len());
+                        #elems.chain(::core::iter::repeat_n(#defaults, missing))
+                    },)*)
+                }
+            }
+        }
+        .to_tokens(&mut out);
+    }
+    Ok(out)
+}
+
+#[inline]
+fn merge_axes_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
+    if !ts.is_empty() {
+        return Err(syn::Error::new(ts.span(), "This macro takes no arguments"));
+    }
+    let mut out = TokenStream::new();
+    for endc in START_CHAR..END_CHAR {
+        let chars = START_CHAR..=endc;
+        let index = proc_macro2::Literal::usize_unsuffixed(chars.len() - 1);
+        let types: Vec<_> = chars.clone().map(|ref c| cr2i(c)).collect();
+        let elems: Vec<_> = chars.map(|ref c| cr2i(&(c + TO_LOWERCASE))).collect();
+        let (left, right) = (cr2i(&endc), cr2i(&(endc + 1)));
+        let (left_elem, right_elem) = (
+            cr2i(&(endc + TO_LOWERCASE)),
+            cr2i(&(endc + 1 + TO_LOWERCASE)),
+        );
+        let nested = types
+            .iter()
+            .rfold(quote!((#right, Tail)), |acc, ty| quote!((#ty, #acc)));
+        let merged = types.iter().rfold(quote!(Tail), |acc, ty| {
+            if *ty == left {
+                quote!((Product<#left, #right>, #acc))
+            } else {
+                quote!((#ty, #acc))
+            }
+        });
+        let pattern = elems.iter().rfold(
+            quote!((#right_elem, tail)),
+            |acc, elem| quote!((#elem, #acc)),
+        );
+        let rebuilt = elems.iter().rfold(quote!(tail), |acc, elem| {
+            if *elem == left_elem {
+                quote!((Product::new(#elem, #right_elem), #acc))
+            } else {
+                quote!((#elem, #acc))
+            }
+        });
+        quote! {
+            impl<#(#types,)* #right: Iterator + Clone, Tail: sealed::Nested> MergeAxes<#index> for #nested
+            where
+                #left: Iterator,
+                #left::Item: Clone,
+            {
+                type Merged = #merged;
+                #[inline(always)]
+                fn merge_axes(self) -> Self::Merged {
+                    let #pattern = self;
+                    #rebuilt
+                }
+            }
+        }
+        .to_tokens(&mut out);
+    }
+    Ok(out)
+}
+
+#[inline]
+fn zip_n_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
+    if !ts.is_empty() {
+        return Err(syn::Error::new(ts.span(), "This macro takes no arguments"));
+    }
+    let mut out = TokenStream::new();
+    for endc in START_CHAR..=END_CHAR {
+        let chars = START_CHAR..=endc;
+        let n = chars.len();
+        let name = syn::Ident::new(&format!("breadth_first_zip{n}"), Span::call_site());
+        let types: Vec<_> = chars.clone().map(|ref c| cr2i(c)).collect();
+        let elems: Vec<_> = chars.map(|ref c| cr2i(&(c + TO_LOWERCASE))).collect();
+        let summary = format!(
+            " Breadth-first zip over {n} iterator{}: shorthand for `({}{}).breadth_first()` with the arity in its name.",
+            if n == 1 { "" } else { "s" },
+            elems.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "),
+            if n == 1 { "," } else { "" },
+        );
+        let example = format!(
+            " let zip = breadth_first_zip::{name}({});\n assert_eq!(zip.next(), Some(({})));",
+            vec!["0..2_u8"; n].join(", "),
+            vec!["&0"; n].join(", ") + if n == 1 { "," } else { "" },
+        );
+        quote! {
+            #[doc = #summary]
+            #[doc = " ```"]
+            #[doc = #example]
+            #[doc = " ```"]
+            #[allow(clippy::too_many_arguments)]
+            #[inline(always)]
+            #[must_use]
+            pub fn #name<'item, #(#types: ::core::iter::Iterator),*>(
+                #(#elems: #types),*
+            ) -> BreadthFirstManager<'item, <(#(#types,)*) as BreadthFirstZip<'item>>::Nested>
+            where
+                #(#types::Item: 'item),*
+            {
+                (#(#elems,)*).breadth_first()
+            }
+        }
+        .to_tokens(&mut out);
+    }
+    Ok(out)
+}
+
+#[inline]
+fn owning_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
+    if !ts.is_empty() {
+        return Err(syn::Error::new(ts.span(), "This macro takes no arguments"));
+    }
+    let mut out = TokenStream::new();
+    for endc in START_CHAR..=END_CHAR {
+        let chars = START_CHAR..=endc;
+        let types: Vec<_> = chars.clone().map(|ref c| cr2i(c)).collect();
+        let elems: Vec<_> = chars.map(|ref c| cr2i(&(c + TO_LOWERCASE))).collect();
+        quote! {
+            impl<#(#types: 'static),*> IntoOwning for (#(Vec<#types>,)*) {
+                type Owning = BreadthFirstManager<
+                    'static,
+                    <(#(::alloc::vec::IntoIter<#types>,)*) as BreadthFirstZip<'static>>::Nested,
+                >;
+                #[inline(always)]
+                fn into_owning(self) -> Self::Owning {
+                    let (#(#elems,)*) = self;
+                    (#(#elems.into_iter(),)*).breadth_first()
+                }
+            }
+        }
+        .to_tokens(&mut out);
+    }
+    Ok(out)
+}
+
+#[inline]
+fn roundtrip_tests_fallible(ts: TokenStream) -> syn::Result<TokenStream> {
+    if !ts.is_empty() {
+        return Err(syn::Error::new(ts.span(), "This macro takes no arguments"));
+    }
+    let mut tests = TokenStream::new();
+    for endc in START_CHAR..=END_CHAR {
+        let chars = START_CHAR..=endc;
+        let name = syn::Ident::new(&format!("arity_{}", chars.len()), Span::call_site());
+        let values: Vec<_> = chars
+            .clone()
+            .map(|c| proc_macro2::Literal::u8_suffixed(c - START_CHAR))
+            .collect();
+        let nested = values
+            .iter()
+            .rfold(quote!(()), |acc, value| quote!((#value, #acc)));
+        quote! {
+            #[test]
+            fn #name() {
+                let manager = crate::BreadthFirstManager::new(crate::BreadthFirstZip::unflatten((
+                    #(::core::iter::once(#values),)*
+                )));
+                assert_eq!(manager.next(), Some((#(&#values,)*)));
+                assert_eq!(manager.next(), None);
+                assert_eq!(crate::Flatten::flatten(#nested), (#(#values,)*));
+            }
+        }
+        .to_tokens(&mut tests);
+    }
+    Ok(quote! {
+        /// Every arity round-trips through `unflatten` and `flatten`, including the special-cased single-element tuple.
+        mod roundtrip {
+            #tests
+        }
+    })
+}
+
 #[inline]
 fn fallible(ts: TokenStream) -> syn::Result<TokenStream> {
     if !ts.is_empty() {
@@ -144,7 +577,7 @@ fn impl_generics(
                 spans: [Span::call_site()],
             }),
             bounds: {
-                let iterator = syn::TypeParamBound::Trait(syn::TraitBound {
+                let into_iterator = syn::TypeParamBound::Trait(syn::TraitBound {
                     paren_token: None,
                     modifier: syn::TraitBoundModifier::None,
                     lifetimes: None,
@@ -162,7 +595,7 @@ fn impl_generics(
                                 arguments: syn::PathArguments::None,
                             },
                             syn::PathSegment {
-                                ident: syn::Ident::new("Iterator", Span::call_site()),
+                                ident: syn::Ident::new("IntoIterator", Span::call_site()),
                                 arguments: syn::PathArguments::None,
                             },
                         ]
@@ -170,7 +603,7 @@ fn impl_generics(
                         .collect(),
                     },
                 });
-                [iterator].into_iter().collect()
+                [into_iterator].into_iter().collect()
             },
             eq_token: None,
             default: None,
@@ -292,18 +725,7 @@ fn huge_nested_type(chars: RangeInclusive<u8>) -> syn::Result<syn::Type> {
                                         apostrophe: Span::call_site(),
                                         ident: syn::Ident::new("item", Span::call_site()),
                                     }),
-                                    syn::GenericArgument::Type(syn::Type::Path(syn::TypePath {
-                                        qself: None,
-                                        path: syn::Path {
-                                            leading_colon: None,
-                                            segments: [syn::PathSegment {
-                                                ident: cr2i(c),
-                                                arguments: syn::PathArguments::None,
-                                            }]
-                                            .into_iter()
-                                            .collect(),
-                                        },
-                                    })),
+                                    syn::GenericArgument::Type(into_iter_type(c)),
                                     syn::GenericArgument::Type(acc),
                                 ]
                                 .into_iter()
@@ -322,6 +744,13 @@ fn huge_nested_type(chars: RangeInclusive<u8>) -> syn::Result<syn::Type> {
     )
 }
 
+/// `<A as IntoIterator>::IntoIter`, i.e. the iterator a tuple element becomes once zipped.
+#[inline]
+fn into_iter_type(c: &u8) -> syn::Type {
+    let ident = cr2i(c);
+    syn::Type::Verbatim(quote!(<#ident as ::core::iter::IntoIterator>::IntoIter))
+}
+
 #[inline]
 fn huge_nested_tuple(chars: RangeInclusive<u8>) -> syn::Result<syn::Type> {
     Ok(chars.rfold(syn::parse2(quote!(()))?, |acc, ref c| {
@@ -517,19 +946,12 @@ fn fn_unflatten(chars: RangeInclusive<u8>) -> syn::Result<syn::ImplItem> {
                         })),
                         paren_token: paren_token(),
                         args: [
-                            syn::Expr::Path(syn::ExprPath {
-                                attrs: vec![],
-                                qself: None,
-                                path: syn::Path {
-                                    leading_colon: None,
-                                    segments: [syn::PathSegment {
-                                        ident: cr2i(&(c + TO_LOWERCASE)),
-                                        arguments: syn::PathArguments::None,
-                                    }]
-                                    .into_iter()
-                                    .collect(),
-                                },
-                            }),
+                            {
+                                let elem = cr2i(&(c + TO_LOWERCASE));
+                                syn::Expr::Verbatim(
+                                    quote!(::core::iter::IntoIterator::into_iter(#elem)),
+                                )
+                            },
                             acc,
                         ]
                         .into_iter()
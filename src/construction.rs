@@ -0,0 +1,343 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Build a breadth-first zip one iterator at a time instead of from a tuple, from a runtime sequence of iterators, or in a `const` context.
+
+use crate::{
+    sealed, BaseCase, BreadthFirst, BreadthFirstManager, BreadthFirstZip, BreadthFirstZipped,
+    Flatten,
+};
+use ::core::cell::Cell;
+
+/// Add an element to the right-hand end of a nested tuple, e.g. `(A, (B, ()))` to `(A, (B, (C, ())))`.
+pub trait Append<Last>: sealed::Nested {
+    /// Nested tuple with `Last` at the end.
+    type Appended: sealed::Nested;
+    /// Add `last` to the end.
+    #[must_use]
+    fn append(self, last: Last) -> Self::Appended;
+}
+
+impl<Last> Append<Last> for () {
+    type Appended = (Last, ());
+    #[inline(always)]
+    fn append(self, last: Last) -> Self::Appended {
+        (last, ())
+    }
+}
+
+impl<Head, Tail: Append<Last>, Last> Append<Last> for (Head, Tail) {
+    type Appended = (Head, Tail::Appended);
+    #[inline(always)]
+    fn append(self, last: Last) -> Self::Appended {
+        (self.0, self.1.append(last))
+    }
+}
+
+/// Turn a nested tuple of iterators like `(A, (B, ()))` into `BreadthFirstZipped<A, BreadthFirstZipped<B, BaseCase>>`.
+pub trait Nest<'item>: sealed::Nested {
+    /// Recursive implementation of a breadth-first zip over these iterators.
+    type Nested: BreadthFirst<'item>;
+    /// Wrap each iterator, from right to left.
+    #[must_use]
+    fn nest(self) -> Self::Nested;
+}
+
+impl<'item> Nest<'item> for () {
+    type Nested = BaseCase;
+    #[inline(always)]
+    fn nest(self) -> Self::Nested {
+        BaseCase(Cell::new(true))
+    }
+}
+
+impl<'item, Head: Iterator, Tail: Nest<'item>> Nest<'item> for (Head, Tail)
+where
+    Head::Item: 'item,
+    (
+        &'item Head::Item,
+        <Tail::Nested as BreadthFirst<'item>>::Advance,
+    ): Flatten,
+{
+    type Nested = BreadthFirstZipped<'item, Head, Tail::Nested>;
+    #[inline(always)]
+    fn nest(self) -> Self::Nested {
+        BreadthFirstZipped::new(self.0, self.1.nest())
+    }
+}
+
+/// Replace the `I`th iterator in a nested tuple like `(A, (B, (C, ())))` with a `map`ped version of itself.
+pub trait MapAxis<const I: usize, F>: sealed::Nested {
+    /// Nested tuple with the `I`th iterator mapped.
+    type Mapped: sealed::Nested;
+    /// Map each element of the `I`th iterator through `f`.
+    #[must_use]
+    fn map_axis(self, f: F) -> Self::Mapped;
+}
+
+breadth_first_zip_macros::implement_map_axis!(); // Implement for (A, Tail), (A, (B, Tail)), (A, (B, (C, Tail))), ...
+
+/// Every pair of an element of `A` and an element of `B`, in lexicographic order (`B` fastest), like nested `for` loops.
+/// Restarts `B` from a clone of the original for each element of `A`.
+pub struct Product<A: Iterator, B> {
+    /// Outer iterator.
+    a: A,
+    /// Original inner iterator, cloned once per element of `a`.
+    b: B,
+    /// Current element of `a` and what's left of `b` to pair it with.
+    current: Option<(A::Item, B)>,
+}
+
+impl<A: Iterator, B: Iterator + Clone> Product<A, B> {
+    /// Pair every element of `a` with every element of `b`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(a: A, b: B) -> Self {
+        Self {
+            a,
+            b,
+            current: None,
+        }
+    }
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<A: Iterator, B: Iterator + Clone> Iterator for Product<A, B>
+where
+    A::Item: Clone,
+{
+    type Item = (A::Item, B::Item);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(current) = self.current.as_mut() {
+                if let Some(right) = current.1.next() {
+                    return Some((current.0.clone(), right));
+                }
+            }
+            self.current = Some((self.a.next()?, self.b.clone()));
+        }
+    }
+}
+
+/// Replace the `I`th and `I + 1`th iterators in a nested tuple like `(A, (B, (C, ())))` with their `Product`.
+pub trait MergeAxes<const I: usize>: sealed::Nested {
+    /// Nested tuple with one fewer iterator.
+    type Merged: sealed::Nested;
+    /// Merge the `I`th and `I + 1`th iterators into one over pairs.
+    #[must_use]
+    fn merge_axes(self) -> Self::Merged;
+}
+
+breadth_first_zip_macros::implement_merge_axes!(); // Implement for (A, (B, Tail)), (A, (B, (C, Tail))), ...
+
+/// Collect iterators one at a time (from left to right), then `build` a breadth-first zip over all of them,
+/// for when they don't come as a tuple up front. Same output as zipping the equivalent tuple.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BreadthFirstBuilder<Iters: sealed::Nested>(Iters);
+
+impl BreadthFirstBuilder<()> {
+    /// Start with no iterators.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(())
+    }
+}
+
+impl<Iters: sealed::Nested> BreadthFirstBuilder<Iters> {
+    /// Add an iterator to the right-hand end.
+    #[inline(always)]
+    #[must_use]
+    pub fn push_axis<Iter: Iterator>(self, iter: Iter) -> BreadthFirstBuilder<Iters::Appended>
+    where
+        Iters: Append<Iter>,
+    {
+        BreadthFirstBuilder(self.0.append(iter))
+    }
+    /// Transform each element of the `I`th iterator added so far (from the left, starting at 0) before it's cached,
+    /// so `f` runs once per element no matter how many combinations it ends up in.
+    /// `f` needn't be `Clone`: like every other iterator here, the mapped one is pulled once, never cloned or re-created.
+    #[inline(always)]
+    #[must_use]
+    pub fn map_axis<const I: usize, F>(self, f: F) -> BreadthFirstBuilder<Iters::Mapped>
+    where
+        Iters: MapAxis<I, F>,
+    {
+        BreadthFirstBuilder(self.0.map_axis(f))
+    }
+    /// Merge the `I`th and `I + 1`th iterators added so far into one iterator over pairs (in lexicographic order), leaving one fewer axis.
+    /// Explores the same combinations, but in a different order: index sums count each pair as one step along the merged axis,
+    /// so e.g. `(x1, y0)` comes only after every `(x0, y)`, instead of alongside `(x0, y1)`. The `I + 1`th iterator must be `Clone`.
+    #[inline(always)]
+    #[must_use]
+    pub fn merge_axes<const I: usize>(self) -> BreadthFirstBuilder<Iters::Merged>
+    where
+        Iters: MergeAxes<I>,
+    {
+        BreadthFirstBuilder(self.0.merge_axes())
+    }
+    /// Zip every iterator added so far.
+    #[inline(always)]
+    #[must_use]
+    pub fn build<'item>(self) -> BreadthFirstManager<'item, Iters::Nested>
+    where
+        Iters: Nest<'item>,
+    {
+        BreadthFirstManager::new(self.0.nest())
+    }
+}
+
+/// Either `BaseCase` or a `BreadthFirstZipped` chain over iterators all of type `Iter`, buildable from a runtime sequence of them.
+pub trait NestFrom<Iter>: Sized + sealed::BreadthFirst {
+    /// Take one iterator from `axes` for each level of nesting, from left to right,
+    /// or return `None` if there are too few or too many.
+    #[must_use]
+    fn nest_from<Axes: Iterator<Item = Iter>>(axes: &mut Axes) -> Option<Self>;
+}
+
+impl<Iter> NestFrom<Iter> for BaseCase {
+    #[inline(always)]
+    fn nest_from<Axes: Iterator<Item = Iter>>(axes: &mut Axes) -> Option<Self> {
+        axes.next().is_none().then(|| Self(Cell::new(true)))
+    }
+}
+
+impl<'item, Iter: Iterator, Tail: BreadthFirst<'item> + NestFrom<Iter>> NestFrom<Iter>
+    for BreadthFirstZipped<'item, Iter, Tail>
+{
+    #[inline(always)]
+    fn nest_from<Axes: Iterator<Item = Iter>>(axes: &mut Axes) -> Option<Self> {
+        let head = axes.next()?;
+        Some(Self::new(head, Tail::nest_from(axes)?))
+    }
+}
+
+/// Zip a runtime sequence of iterators (e.g. a `Vec` of boxed iterators read from config) into a nested breadth-first zip,
+/// like the tuple macros' `unflatten` but at runtime.
+///
+/// The depth is fixed by the return type, e.g. `BreadthFirstZipIter<(I, I, I)>`,
+/// and this returns `None` if `axes` has a different number of iterators.
+#[inline]
+#[must_use]
+pub fn build_nested<
+    'item,
+    Iter,
+    Nested: BreadthFirst<'item> + NestFrom<Iter>,
+    Axes: IntoIterator<Item = Iter>,
+>(
+    axes: Axes,
+) -> Option<BreadthFirstManager<'item, Nested>> {
+    Nested::nest_from(&mut axes.into_iter()).map(BreadthFirstManager::new)
+}
+
+/// Flat tuple of finite iterators like `(A, B, C)`, each of which can be padded with a default value to the length of the longest.
+pub trait Pad {
+    /// One default value per iterator, e.g. `(A::Item, B::Item, C::Item)`.
+    type Defaults;
+    /// Each iterator followed by enough copies of its default to match the longest.
+    type Padded;
+    /// Pad each iterator with copies of its default until it's as long as the longest, like `itertools::zip_longest`.
+    #[must_use]
+    fn pad(self, defaults: Self::Defaults) -> Self::Padded;
+}
+
+breadth_first_zip_macros::implement_pad!(); // Implement for (A,), (A, B), (A, B, C), ...
+
+/// Breadth-first zip over a tuple of finite iterators, padding each shorter one with copies of its default value
+/// (from `defaults`, in the same order) so that every iterator is as long as the longest.
+///
+/// Without padding, a shorter iterator just stops contributing new indices;
+/// with it, every combination of positions up to the longest length comes out, defaults and all.
+#[inline]
+#[must_use]
+pub fn breadth_first_zip_padded<'item, Iters: Pad>(
+    iters: Iters,
+    defaults: Iters::Defaults,
+) -> BreadthFirstManager<'item, <Iters::Padded as BreadthFirstZip<'item>>::Nested>
+where
+    Iters::Padded: BreadthFirstZip<'item>,
+{
+    iters.pad(defaults).breadth_first()
+}
+
+/// Iterator over `f(0)`, `f(1)`, `f(2)`, ... up to (not including) the first `None`, e.g. for a procedurally generated axis.
+///
+/// Zipped, each index is evaluated exactly once, in order, and only when a combination first needs it:
+/// every element is cached from then on, so `f` is never called again for the same index, even after `reset`.
+#[derive(Clone, Debug)]
+pub struct LazyAxis<F> {
+    /// Element at each index, or `None` past the end.
+    f: F,
+    /// Index to evaluate next, or `None` once `f` has returned `None` (or we've run out of indices).
+    index: Option<usize>,
+}
+
+impl<F> LazyAxis<F> {
+    /// Iterator over `f(0)`, `f(1)`, `f(2)`, ... up to (not including) the first `None`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn from_index_fn(f: F) -> Self {
+        Self { f, index: Some(0) }
+    }
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<T, F: FnMut(usize) -> Option<T>> Iterator for LazyAxis<F> {
+    type Item = T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index?;
+        let value = (self.f)(index);
+        self.index = value.as_ref().and_then(|_| index.checked_add(1));
+        value
+    }
+}
+
+/// Iterator over a borrowed slice that can be built in a `const` context, e.g. to zip tables in `static`s without copying them:
+/// like `slice::Iter`, but with a `const` constructor.
+///
+/// A `const` tuple of these (or of arrays) is all the setup a zip needs: for example,
+/// `const AXES: (ConstAxis<'static, u8>, [char; 2]) = (ConstAxis::new(&[0, 1, 2]), ['a', 'b']);` and then `AXES.breadth_first()`.
+/// `const` stops short of the zip itself: each iterator gets wrapped in a caching `Reiterator`, whose constructor isn't a `const fn`,
+/// and `BreadthFirstZip::breadth_first` is a trait method, which can't be `const` on stable Rust.
+/// So that last step happens at runtime, but it's cheap and never allocates.
+/// Past that point, only `BreadthFirstManager::new` and `BreadthFirstManager::with_schedule` are `const`.
+#[derive(Clone, Copy, Debug)]
+pub struct ConstAxis<'elements, T> {
+    /// Everything this iterator will yield (from `index` on).
+    elements: &'elements [T],
+    /// Index of the element to yield next.
+    index: usize,
+}
+
+impl<'elements, T> ConstAxis<'elements, T> {
+    /// Iterator over references to each element of `elements`, in order.
+    #[inline(always)]
+    #[must_use]
+    pub const fn new(elements: &'elements [T]) -> Self {
+        Self { elements, index: 0 }
+    }
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<'elements, T> Iterator for ConstAxis<'elements, T> {
+    type Item = &'elements T;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let element = self.elements.get(self.index)?;
+        self.index = self.index.checked_add(1)?;
+        Some(element)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.elements.len().saturating_sub(self.index);
+        (remaining, Some(remaining))
+    }
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<T> ExactSizeIterator for ConstAxis<'_, T> {}
@@ -0,0 +1,29 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Cooperative cancellation, e.g. to stop an exhaustive search running on another thread.
+
+use crate::{BreadthFirst, BreadthFirstManager, Flatten};
+use ::core::sync::atomic::{AtomicBool, Ordering};
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Iterator checking `cancel` before each combination and returning `None` once it's set.
+    /// Share the flag with e.g. `Arc<AtomicBool>` or a scoped thread; the zip stays where it is, so clearing the flag picks up where it left off.
+    #[inline]
+    pub fn with_cancel(
+        &'item self,
+        cancel: &'item AtomicBool,
+    ) -> impl Iterator<Item = <Tail::Advance as Flatten>::Flattened> + 'item {
+        ::core::iter::from_fn(|| {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
+            }
+            self.next()
+        })
+    }
+}
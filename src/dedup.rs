@@ -0,0 +1,30 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Skip combinations whose values (not just indices) we've already seen, for iterators with repeated elements.
+
+use crate::{BreadthFirst, BreadthFirstManager, Flatten};
+use ::core::hash::Hash;
+use ::std::collections::HashSet;
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Iterator skipping any combination equal (by value) to one it's already yielded,
+    /// so iterators with repeated elements don't have to be deduplicated beforehand.
+    /// Memory grows with the number of distinct combinations: it keeps one tuple of references per combination yielded.
+    #[inline]
+    pub fn unique_values(
+        &'item self,
+    ) -> impl Iterator<Item = <Tail::Advance as Flatten>::Flattened> + 'item
+    where
+        <Tail::Advance as Flatten>::Flattened: Clone + Eq + Hash,
+    {
+        let mut seen = HashSet::new();
+        ::core::iter::from_fn(|| self.next())
+            .filter(move |combination| seen.insert(combination.clone()))
+    }
+}
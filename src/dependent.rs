@@ -0,0 +1,248 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Breadth-first `zip` where the last iterator depends on the earlier ones' elements, like `flat_map`.
+//!
+//! Use `breadth_first_flat_zip` for exactly two iterators, or `BreadthFirstManager::with_dependent_last` after any number of independent ones.
+
+use crate::{BreadthFirst, BreadthFirstManager, Flatten, Reiterate, Reiterator};
+use ::core::{cell::Cell, convert::Infallible, marker::PhantomData};
+
+/// Pair each element of `head` with a (lazy) iterator built from it.
+pub struct Dependents<Head: Iterator, F> {
+    /// Independent iterator.
+    head: Head,
+    /// Build an iterator from an element of `head`.
+    tail: F,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<Head: Iterator, Tail: Iterator, F: Fn(&Head::Item) -> Tail> Iterator for Dependents<Head, F> {
+    type Item = (Head::Item, Reiterator<Tail>);
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let head = self.head.next()?;
+        let tail = (self.tail)(&head).reiterate();
+        Some((head, tail))
+    }
+}
+
+/// Breadth-first zip over pairs `(a, b)` where `b` comes from an iterator built from `a`,
+/// in the same order (by index sum, then lexicographically) as a zip over independent iterators.
+pub struct FlatZipped<'item, Head: Iterator, Tail: Iterator, F: Fn(&Head::Item) -> Tail> {
+    /// Enumerated caching iterator over each element of `head` alongside its own caching iterator.
+    iter: Reiterator<Dependents<Head, F>>,
+    /// Sum of indices currently being visited.
+    index_sum: Cell<usize>,
+    /// Index into `head` to try next at the current index sum.
+    head_index: Cell<usize>,
+    /// Whether we've yielded anything at the current index sum.
+    yielded: Cell<bool>,
+    /// Representation of this struct's lifetime.
+    lifetime: PhantomData<&'item Infallible>,
+}
+
+impl<'item, Head: Iterator, Tail: Iterator, F: Fn(&Head::Item) -> Tail>
+    FlatZipped<'item, Head, Tail, F>
+where
+    Head::Item: 'item,
+    Tail::Item: 'item,
+{
+    /// Like `Iterator::next` but with a generic lifetime, like `BreadthFirstManager::next`.
+    /// Terminates once an index sum yields nothing and `head` has run out,
+    /// so an infinite `head` whose dependent iterators are all empty never does.
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    #[must_use]
+    pub fn next(&'item self) -> Option<(&'item Head::Item, &'item Tail::Item)> {
+        loop {
+            let index_sum = self.index_sum.get();
+            let head_index = self.head_index.get();
+            if head_index > index_sum {
+                self.next_index_sum()?;
+                continue;
+            }
+            self.iter.index.set(head_index);
+            let Some(indexed) = self.iter.get() else {
+                if !self.yielded.get() {
+                    return None; // `head` ran out and nothing else can show up at higher index sums
+                }
+                self.next_index_sum()?;
+                continue;
+            };
+            self.head_index.set(head_index.checked_add(1)?);
+            let (head, tail) = (&indexed.value.0, &indexed.value.1);
+            tail.index.set(index_sum.checked_sub(head_index)?);
+            if let Some(dependent) = tail.get() {
+                self.yielded.set(true);
+                return Some((head, dependent.value));
+            }
+        }
+    }
+    /// Move on to the next index sum, starting again from the first element of `head`.
+    #[inline(always)]
+    fn next_index_sum(&self) -> Option<()> {
+        self.index_sum.set(self.index_sum.get().checked_add(1)?);
+        self.head_index.set(0);
+        self.yielded.set(false);
+        Some(())
+    }
+}
+
+/// Zip `head` with iterators built from each of its elements, breadth-first: for example,
+/// `breadth_first_flat_zip(0..3, |&a| 0..a)` visits every `(a, b)` with `b < a`.
+///
+/// Only ever two iterators. For more, where the last depends on all the earlier ones,
+/// zip the independent ones as usual and call `BreadthFirstManager::with_dependent_last`.
+/// Chains where a middle iterator depends on earlier ones (and later ones on it) aren't supported.
+#[inline]
+#[must_use]
+pub fn breadth_first_flat_zip<'item, Head: Iterator, Tail: Iterator, F: Fn(&Head::Item) -> Tail>(
+    head: Head,
+    tail: F,
+) -> FlatZipped<'item, Head, Tail, F> {
+    FlatZipped {
+        iter: Dependents { head, tail }.reiterate(),
+        index_sum: Cell::new(0),
+        head_index: Cell::new(0),
+        yielded: Cell::new(false),
+        lifetime: PhantomData,
+    }
+}
+
+/// Pair each remaining combination of a zip with its index sum and a (lazy) iterator built from it.
+pub struct Prefixes<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>, F> {
+    /// Zip over every iterator but the last.
+    manager: &'item BreadthFirstManager<'item, Tail, Schedule>,
+    /// Build the last iterator from a combination of the others.
+    last: F,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<
+        'item,
+        Tail: BreadthFirst<'item>,
+        Schedule: Iterator<Item = usize>,
+        Last: Iterator,
+        F: Fn(&<Tail::Advance as Flatten>::Flattened) -> Last,
+    > Iterator for Prefixes<'item, Tail, Schedule, F>
+{
+    type Item = (
+        usize,
+        <Tail::Advance as Flatten>::Flattened,
+        Reiterator<Last>,
+    );
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let prefix = self.manager.next()?;
+        let index_sum = self.manager.index_sum.get()?;
+        let last = (self.last)(&prefix).reiterate();
+        Some((index_sum, prefix, last))
+    }
+}
+
+/// Breadth-first zip whose last iterator is built from the current elements of all the others,
+/// in the same order (by index sum, then lexicographically) as a zip over independent iterators.
+pub struct WithDependentLast<
+    'item,
+    Tail: BreadthFirst<'item>,
+    Schedule: Iterator<Item = usize>,
+    Last: Iterator,
+    F: Fn(&<Tail::Advance as Flatten>::Flattened) -> Last,
+> {
+    /// Enumerated caching iterator over each combination of the earlier iterators alongside its own caching last iterator.
+    iter: Reiterator<Prefixes<'item, Tail, Schedule, F>>,
+    /// Sum of indices currently being visited.
+    index_sum: Cell<usize>,
+    /// Index into the combinations of the earlier iterators to try next at the current index sum.
+    prefix_index: Cell<usize>,
+    /// Whether we've yielded anything at the current index sum.
+    yielded: Cell<bool>,
+}
+
+impl<
+        'item,
+        Tail: BreadthFirst<'item>,
+        Schedule: Iterator<Item = usize>,
+        Last: Iterator,
+        F: Fn(&<Tail::Advance as Flatten>::Flattened) -> Last,
+    > WithDependentLast<'item, Tail, Schedule, Last, F>
+where
+    Last::Item: 'item,
+{
+    /// Like `Iterator::next` but with a generic lifetime, like `BreadthFirstManager::next`.
+    /// Terminates once an index sum yields nothing and the earlier iterators have run out.
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    #[must_use]
+    pub fn next(
+        &'item self,
+    ) -> Option<(
+        &'item <Tail::Advance as Flatten>::Flattened,
+        &'item Last::Item,
+    )> {
+        loop {
+            let index_sum = self.index_sum.get();
+            let prefix_index = self.prefix_index.get();
+            self.iter.index.set(prefix_index);
+            let Some(indexed) = self.iter.get() else {
+                if !self.yielded.get() {
+                    return None; // no last iterator has anything left at this index or beyond
+                }
+                self.next_index_sum()?;
+                continue;
+            };
+            let (prefix, last) = (&indexed.value.1, &indexed.value.2);
+            let Some(last_index) = index_sum.checked_sub(indexed.value.0) else {
+                self.next_index_sum()?; // every later combination has at least this index sum too
+                continue;
+            };
+            self.prefix_index.set(prefix_index.checked_add(1)?);
+            last.index.set(last_index);
+            if let Some(dependent) = last.get() {
+                self.yielded.set(true);
+                return Some((prefix, dependent.value));
+            }
+        }
+    }
+    /// Move on to the next index sum, starting again from the first combination of the earlier iterators.
+    #[inline(always)]
+    fn next_index_sum(&self) -> Option<()> {
+        self.index_sum.set(self.index_sum.get().checked_add(1)?);
+        self.prefix_index.set(0);
+        self.yielded.set(false);
+        Some(())
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Append one more iterator built from each combination of these, e.g. the valid completions of a partial assignment:
+    /// `(0..3, 0..3).breadth_first().with_dependent_last(|&(&a, &b)| 0..=(a + b))` visits every `(a, b, c)` with `c <= a + b`.
+    /// Each element of the last iterator adds one to the index sum, as if it were zipped like the rest.
+    /// Consumes the remaining combinations of this zip (lazily), stopping the first time `next` would return `None`.
+    #[inline]
+    #[must_use]
+    pub fn with_dependent_last<
+        Last: Iterator,
+        F: Fn(&<Tail::Advance as Flatten>::Flattened) -> Last,
+    >(
+        &'item self,
+        f: F,
+    ) -> WithDependentLast<'item, Tail, Schedule, Last, F> {
+        WithDependentLast {
+            iter: Prefixes {
+                manager: self,
+                last: f,
+            }
+            .reiterate(),
+            index_sum: Cell::new(0),
+            prefix_index: Cell::new(0),
+            yielded: Cell::new(false),
+        }
+    }
+}
@@ -0,0 +1,310 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Peek at the internals of a breadth-first zip while it runs, e.g. to check its invariants in tests.
+
+use crate::{
+    homogeneous::IntoArray, BaseCase, BreadthFirst, BreadthFirstManager, BreadthFirstZipped,
+    Flatten,
+};
+use ::core::mem::size_of;
+
+/// Either `BaseCase` or a sequence of `BreadthFirstZipped` over `ExactSizeIterator`s ending in `BaseCase`.
+pub trait ExactSizeBreadthFirst<'item>: BreadthFirst<'item> {
+    /// Length of each iterator, in the same shape as `indices`, as reported by `ExactSizeIterator::len` when it was zipped.
+    /// Doesn't pull anything.
+    #[must_use]
+    fn lengths(&self) -> Self::Indices;
+    /// Number of elements each iterator contributes to iteration, i.e. its length after `prune` and `truncate_axis`,
+    /// in the same shape as `indices`. Remembers each one, so `max_index_sum` is known from then on.
+    /// Pulls (and caches) every element of pruned iterators, to check which ones are kept, but nothing else.
+    #[must_use]
+    fn kept_lengths(&self) -> Self::Indices;
+    /// How much each step along each iterator adds to the index sum, in the same shape as `indices` (see `set_weights`).
+    #[must_use]
+    fn weights(&self) -> Self::Indices;
+    /// Bytes taken up by every element of every iterator once cached (not counting the cache's own bookkeeping).
+    /// Doesn't pull anything, like `lengths`.
+    #[must_use]
+    fn cache_bytes(&self) -> usize;
+}
+
+impl<'item> ExactSizeBreadthFirst<'item> for BaseCase {
+    #[inline(always)]
+    fn lengths(&self) -> Self::Indices {}
+    #[inline(always)]
+    fn kept_lengths(&self) -> Self::Indices {}
+    #[inline(always)]
+    fn weights(&self) -> Self::Indices {}
+    #[inline(always)]
+    fn cache_bytes(&self) -> usize {
+        0
+    }
+}
+
+impl<'item, Head: ExactSizeIterator, Tail: ExactSizeBreadthFirst<'item>>
+    ExactSizeBreadthFirst<'item> for BreadthFirstZipped<'item, Head, Tail>
+where
+    Head::Item: 'item,
+    (&'item Head::Item, Tail::Advance): Flatten,
+{
+    #[inline(always)]
+    fn lengths(&self) -> Self::Indices {
+        (self.hint.0, self.tail.lengths())
+    }
+    #[inline(always)]
+    fn kept_lengths(&self) -> Self::Indices {
+        let kept = self.keep.map_or(self.hint.0, |keep| {
+            (0..)
+                .map_while(|index| self.at(index))
+                .filter(|&element| keep(element))
+                .count()
+        });
+        let truncated = self.max_len.map_or(kept, |max_len| kept.min(max_len));
+        self.len.set(Some(truncated));
+        (truncated, self.tail.kept_lengths())
+    }
+    #[inline(always)]
+    fn weights(&self) -> Self::Indices {
+        (self.weight, self.tail.weights())
+    }
+    #[inline(always)]
+    fn cache_bytes(&self) -> usize {
+        size_of::<Head::Item>()
+            .saturating_mul(self.hint.0)
+            .saturating_add(self.tail.cache_bytes())
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Iterator calling `inspect` with the index into each iterator (from left to right) before yielding each combination.
+    /// Advances this zip as it goes, like `owned`.
+    #[inline]
+    pub fn inspect_indices<F: FnMut(&[usize]) + 'item>(
+        &'item self,
+        mut inspect: F,
+    ) -> impl Iterator<Item = <Tail::Advance as Flatten>::Flattened> + 'item
+    where
+        Tail::Indices: IntoArray,
+        <Tail::Indices as IntoArray>::Array: AsRef<[usize]>,
+    {
+        ::core::iter::from_fn(move || {
+            let nested = self.next_nested()?;
+            inspect(self.tail.indices().into_array().as_ref());
+            Some(nested.flatten())
+        })
+    }
+}
+
+impl<'item, Tail: ExactSizeBreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+where
+    Tail::Indices: IntoArray,
+{
+    /// Length of each iterator, from left to right, e.g. to show the dimensions of the search space.
+    /// Doesn't pull anything: each length comes from `ExactSizeIterator::len` when the iterator was zipped.
+    #[inline]
+    #[must_use]
+    pub fn len_per_axis(&self) -> <Tail::Indices as IntoArray>::Array {
+        self.tail.lengths().into_array()
+    }
+    /// Bytes every cached element will take up once every iterator is exhausted (not counting the cache's own bookkeeping),
+    /// e.g. for capacity planning before spending that memory: doesn't pull anything, like `len_per_axis`.
+    #[inline]
+    #[must_use]
+    pub fn estimate_cache_bytes(&self) -> usize {
+        self.tail.cache_bytes()
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Iterator pairing each combination with its 0-based position, like `enumerate` but without flattening the zip into `owned` first.
+    /// Counts from wherever this zip is now, so call it before anything else to number the whole output.
+    #[inline]
+    pub fn enumerate_combinations(
+        &'item self,
+    ) -> impl Iterator<Item = (usize, <Tail::Advance as Flatten>::Flattened)> + 'item {
+        ::core::iter::from_fn(|| self.next()).enumerate()
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Like `owned().scan(init, f)`, but `f` also gets the index sum of each combination: `f(&mut state, index_sum, combination)`.
+    /// As with `scan`, iteration stops the first time `f` returns `None`.
+    #[inline]
+    pub fn scan_with_level<
+        State: 'item,
+        Output,
+        F: FnMut(&mut State, usize, <Tail::Advance as Flatten>::Flattened) -> Option<Output> + 'item,
+    >(
+        &'item self,
+        init: State,
+        mut f: F,
+    ) -> impl Iterator<Item = Output> + 'item {
+        let mut state = init;
+        ::core::iter::from_fn(move || {
+            let combination = self.next()?;
+            let level = self.index_sum.get()?;
+            f(&mut state, level, combination)
+        })
+    }
+}
+
+impl<'item, Tail: ExactSizeBreadthFirst<'item>> BreadthFirstManager<'item, Tail>
+where
+    Tail::Indices: IntoArray,
+    <Tail::Indices as IntoArray>::Array: AsRef<[usize]>,
+{
+    /// Iterator pairing each combination with the fraction of all combinations yielded so far (including it), e.g. for a progress bar.
+    /// Counts from wherever this zip is now, so call it before anything else to reach exactly `1.0` at the end.
+    /// Counts only what's left after `prune` and `truncate_axis` (see `kept_lengths`), so pruned iterators are pulled up front.
+    #[allow(
+        clippy::as_conversions,
+        clippy::cast_precision_loss,
+        clippy::float_arithmetic
+    )] // Lossy only for astronomically many combinations, where a progress bar can't tell the difference
+    #[inline]
+    pub fn progress(
+        &'item self,
+    ) -> impl Iterator<Item = (f64, <Tail::Advance as Flatten>::Flattened)> + 'item {
+        let total: f64 = self
+            .tail
+            .kept_lengths()
+            .into_array()
+            .as_ref()
+            .iter()
+            .map(|&length| length as f64)
+            .product();
+        let mut done = 0_f64;
+        ::core::iter::from_fn(move || {
+            let combination = self.next()?;
+            done += 1_f64;
+            Some((done / total, combination))
+        })
+    }
+}
+
+/// Iterator over every combination from the highest index sum down to the lowest, in the usual order within each index sum.
+pub struct RevLevels<'item, Tail: BreadthFirst<'item>> {
+    /// Zip handing out references.
+    manager: &'item BreadthFirstManager<'item, Tail>,
+    /// Index sum currently being visited, or `None` once we've finished index sum zero.
+    index_sum: Option<usize>,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<'item, Tail: BreadthFirst<'item>> Iterator for RevLevels<'item, Tail> {
+    type Item = <Tail::Advance as Flatten>::Flattened;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let index_sum = self.index_sum?;
+            if let Some(combination) = self.manager.tail.next(index_sum) {
+                return Some(combination.flatten());
+            }
+            self.index_sum = index_sum.checked_sub(1);
+            self.manager.tail.rewind();
+        }
+    }
+}
+
+impl<'item, Tail: ExactSizeBreadthFirst<'item>> BreadthFirstManager<'item, Tail>
+where
+    Tail::Indices: IntoArray,
+    <Tail::Indices as IntoArray>::Array: AsRef<[usize]>,
+{
+    /// Every combination from the highest index sum down to the lowest (e.g. the last combination first), in the usual order within each index sum.
+    /// Finds the highest index sum up front with `kept_lengths`, so it accounts for weights, `prune`, and `truncate_axis`.
+    /// Moves this zip, so e.g. `reset` afterward to iterate in the usual order.
+    #[inline]
+    #[must_use]
+    pub fn rev_levels(&'item self) -> RevLevels<'item, Tail> {
+        let (top, _) = self.top_level();
+        self.index_sum.set(None);
+        self.tail.rewind();
+        RevLevels {
+            manager: self,
+            index_sum: top,
+        }
+    }
+    /// Last combination (in the usual order) satisfying `predicate`, searching from the highest index sum down
+    /// and stopping at the first index sum with any match, so it never visits anything below that.
+    /// Finds the highest index sum up front like `rev_levels`, so it accounts for weights, `prune`, and `truncate_axis`.
+    /// Moves this zip, so e.g. `reset` afterward to iterate in the usual order.
+    #[inline]
+    #[must_use]
+    pub fn rfind<Predicate: FnMut(&<Tail::Advance as Flatten>::Flattened) -> bool>(
+        &'item self,
+        predicate: Predicate,
+    ) -> Option<<Tail::Advance as Flatten>::Flattened> {
+        let (top, _) = self.top_level();
+        self.search_back(top, predicate)
+            .map(|(_, combination)| combination)
+    }
+    /// Position (counting from the first combination, like `Iterator::rposition`) of the last combination satisfying `predicate`,
+    /// searching backward like `rfind`. Moves this zip, like `rfind`.
+    #[inline]
+    #[must_use]
+    pub fn rposition<Predicate: FnMut(<Tail::Advance as Flatten>::Flattened) -> bool>(
+        &'item self,
+        mut predicate: Predicate,
+    ) -> Option<usize>
+    where
+        <Tail::Advance as Flatten>::Flattened: Copy,
+    {
+        let (top, total) = self.top_level();
+        let (after, _) = self.search_back(top, |combination| predicate(*combination))?;
+        total.checked_sub(after)?.checked_sub(1)
+    }
+    /// Highest index sum of any combination (or `None` if there aren't any) and how many combinations there are in total.
+    #[inline]
+    fn top_level(&self) -> (Option<usize>, usize) {
+        let total = self
+            .tail
+            .kept_lengths()
+            .into_array()
+            .as_ref()
+            .iter()
+            .fold(1_usize, |total, &length| total.saturating_mul(length));
+        (self.tail.max_index_sum(), total)
+    }
+    /// Last combination satisfying `predicate`, along with how many combinations come after it, searching from index sum `top` down.
+    #[inline]
+    fn search_back<Predicate: FnMut(&<Tail::Advance as Flatten>::Flattened) -> bool>(
+        &'item self,
+        top: Option<usize>,
+        mut predicate: Predicate,
+    ) -> Option<(usize, <Tail::Advance as Flatten>::Flattened)> {
+        let mut after = 0_usize;
+        let mut level = top;
+        self.index_sum.set(None);
+        while let Some(index_sum) = level {
+            self.tail.rewind();
+            let mut level_len = 0_usize;
+            let mut last_match = None;
+            while let Some(nested) = self.tail.next(index_sum) {
+                let combination = nested.flatten();
+                if predicate(&combination) {
+                    last_match = Some((level_len, combination));
+                }
+                level_len = level_len.saturating_add(1);
+            }
+            if let Some((position, combination)) = last_match {
+                let later = level_len.saturating_sub(position).saturating_sub(1);
+                return Some((after.saturating_add(later), combination));
+            }
+            after = after.saturating_add(level_len);
+            level = index_sum.checked_sub(1);
+        }
+        None
+    }
+}
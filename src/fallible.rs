@@ -0,0 +1,66 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Zip iterators over `Result`s, stopping at the first error.
+
+use crate::{sealed, BreadthFirst, BreadthFirstManager, Flatten};
+
+/// Turn a nested tuple of references to `Result`s like `(&Result<A, E>, (&Result<B, E>, ()))`
+/// into either a nested tuple of references to the successful values like `(&A, (&B, ()))` or the first error.
+pub trait TryAll<Error>: sealed::Nested {
+    /// Nested tuple of references to successful values.
+    type Ok: Flatten;
+    /// Either every successful value or the first error, from left to right.
+    /// # Errors
+    /// If any element is an error.
+    fn try_all(self) -> Result<Self::Ok, Error>;
+}
+
+impl<Error> TryAll<Error> for () {
+    type Ok = ();
+    #[inline(always)]
+    fn try_all(self) -> Result<Self::Ok, Error> {
+        Ok(())
+    }
+}
+
+impl<'item, Head, Error, Tail: TryAll<&'item Error>> TryAll<&'item Error>
+    for (&'item Result<Head, Error>, Tail)
+where
+    (&'item Head, Tail::Ok): Flatten,
+{
+    type Ok = (&'item Head, Tail::Ok);
+    #[inline(always)]
+    fn try_all(self) -> Result<Self::Ok, &'item Error> {
+        Ok((self.0.as_ref()?, self.1.try_all()?))
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Zip iterators over `Result`s into combinations of successful values, short-circuiting at the first error:
+    /// the iterator returns `Some(Err(..))` once, then `None` from then on.
+    #[inline]
+    pub fn try_iter<Error>(
+        &'item self,
+    ) -> impl Iterator<
+        Item = Result<<<Tail::Advance as TryAll<Error>>::Ok as Flatten>::Flattened, Error>,
+    > + 'item
+    where
+        Tail::Advance: TryAll<Error>,
+    {
+        let mut failed = false;
+        ::core::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let result = self.next_nested()?.try_all();
+            failed = result.is_err();
+            Some(result.map(Flatten::flatten))
+        })
+    }
+}
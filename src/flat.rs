@@ -0,0 +1,161 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Hand-unrolled breadth-first zips over exactly two or three iterators: flat loops instead of recursion, e.g. for small embedded targets.
+//!
+//! Same output as zipping the equivalent tuple, but not chosen automatically:
+//! `breadth_first` always returns a `BreadthFirstManager` (which every adapter in this crate builds on),
+//! and picking another type for some arities would take specialization, which stable Rust doesn't have.
+
+use crate::{element_at, Reiterate, Reiterator};
+use ::core::cell::Cell;
+
+/// Progress through index sums, shared by every arity.
+struct Frontier {
+    /// Index sum currently being visited.
+    index_sum: Cell<usize>,
+    /// Index into the first iterator of the next candidate.
+    first: Cell<usize>,
+    /// Whether we've yielded anything with the current index sum.
+    found: Cell<bool>,
+    /// Whether we've finished, i.e. found nothing with some index sum (so nothing with any higher index sum, either).
+    done: Cell<bool>,
+}
+
+impl Frontier {
+    /// Start at index sum zero.
+    #[inline(always)]
+    const fn new() -> Self {
+        Self {
+            index_sum: Cell::new(0),
+            first: Cell::new(0),
+            found: Cell::new(false),
+            done: Cell::new(false),
+        }
+    }
+    /// Move on to the next index sum, or return `None` (for good) if the one we just finished had nothing.
+    #[inline(always)]
+    fn next_level(&self) -> Option<()> {
+        let next = self.index_sum.get().checked_add(1);
+        if !self.found.replace(false) || next.is_none() {
+            self.done.set(true);
+            return None;
+        }
+        self.index_sum.set(next?);
+        self.first.set(0);
+        Some(())
+    }
+}
+
+/// Breadth-first zip over exactly two iterators, like `(a, b).breadth_first()` but with a flat loop instead of recursion.
+pub struct BreadthFirstZip2<A: Iterator, B: Iterator> {
+    /// First iterator.
+    a: Reiterator<A>,
+    /// Second iterator.
+    b: Reiterator<B>,
+    /// Progress through index sums.
+    frontier: Frontier,
+}
+
+impl<A: Iterator, B: Iterator> BreadthFirstZip2<A, B> {
+    /// Zip two iterators without pulling anything from either yet.
+    #[inline]
+    #[must_use]
+    pub fn new(a: A, b: B) -> Self {
+        Self {
+            a: a.reiterate(),
+            b: b.reiterate(),
+            frontier: Frontier::new(),
+        }
+    }
+    /// Next combination, in the same order as `(a, b).breadth_first()`.
+    #[allow(clippy::should_implement_trait)] // Can't: each combination borrows from `self`
+    #[inline]
+    #[must_use]
+    pub fn next(&self) -> Option<(&A::Item, &B::Item)> {
+        let frontier = &self.frontier;
+        loop {
+            if frontier.done.get() {
+                return None;
+            }
+            let first = frontier.first.get();
+            let (Some(second), Some(a)) = (
+                frontier.index_sum.get().checked_sub(first),
+                element_at(&self.a, first),
+            ) else {
+                frontier.next_level()?;
+                continue;
+            };
+            frontier.first.set(first.saturating_add(1));
+            if let Some(b) = element_at(&self.b, second) {
+                frontier.found.set(true);
+                return Some((a, b));
+            }
+        }
+    }
+}
+
+/// Breadth-first zip over exactly three iterators, like `(a, b, c).breadth_first()` but with flat loops instead of recursion.
+pub struct BreadthFirstZip3<A: Iterator, B: Iterator, C: Iterator> {
+    /// First iterator.
+    a: Reiterator<A>,
+    /// Second iterator.
+    b: Reiterator<B>,
+    /// Third iterator.
+    c: Reiterator<C>,
+    /// Index into the second iterator of the next candidate.
+    second: Cell<usize>,
+    /// Progress through index sums.
+    frontier: Frontier,
+}
+
+impl<A: Iterator, B: Iterator, C: Iterator> BreadthFirstZip3<A, B, C> {
+    /// Zip three iterators without pulling anything from any of them yet.
+    #[inline]
+    #[must_use]
+    pub fn new(a: A, b: B, c: C) -> Self {
+        Self {
+            a: a.reiterate(),
+            b: b.reiterate(),
+            c: c.reiterate(),
+            second: Cell::new(0),
+            frontier: Frontier::new(),
+        }
+    }
+    /// Next combination, in the same order as `(a, b, c).breadth_first()`.
+    #[allow(clippy::should_implement_trait)] // Can't: each combination borrows from `self`
+    #[inline]
+    #[must_use]
+    pub fn next(&self) -> Option<(&A::Item, &B::Item, &C::Item)> {
+        let frontier = &self.frontier;
+        loop {
+            if frontier.done.get() {
+                return None;
+            }
+            let first = frontier.first.get();
+            let (Some(rest), Some(a)) = (
+                frontier.index_sum.get().checked_sub(first),
+                element_at(&self.a, first),
+            ) else {
+                frontier.next_level()?;
+                self.second.set(0);
+                continue;
+            };
+            let second = self.second.get();
+            let (Some(third), Some(b)) = (rest.checked_sub(second), element_at(&self.b, second))
+            else {
+                frontier.first.set(first.saturating_add(1));
+                self.second.set(0);
+                continue;
+            };
+            self.second.set(second.saturating_add(1));
+            if let Some(c) = element_at(&self.c, third) {
+                frontier.found.set(true);
+                return Some((a, b, c));
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Regroup combinations by the element they share from one iterator, since breadth-first order interleaves them.
+
+use crate::{
+    homogeneous::IntoArray, projection::SplitPrefix, BreadthFirst, BreadthFirstManager, Flatten,
+};
+use ::std::collections::HashMap;
+use alloc::vec::Vec;
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+where
+    Tail::Indices: IntoArray,
+    <Tail::Indices as IntoArray>::Array: AsRef<[usize]>,
+{
+    /// Drain every remaining combination into buckets keyed by index into the first iterator:
+    /// key `i` holds every combination whose first element is the `i`th element of the first iterator, in breadth-first order.
+    #[inline]
+    #[must_use]
+    pub fn group_by_axis0(
+        &'item self,
+    ) -> HashMap<usize, Vec<<Tail::Advance as Flatten>::Flattened>> {
+        let mut groups: HashMap<_, Vec<_>> = HashMap::new();
+        while let Some(combination) = self.next() {
+            if let Some(&index) = self.tail.indices().into_array().as_ref().first() {
+                groups.entry(index).or_default().push(combination);
+            }
+        }
+        groups
+    }
+    /// Drain every remaining combination into groups sharing their first `K` elements,
+    /// each pairing that prefix (e.g. `(&a, &b)`) with the rest of every combination that starts with it (e.g. `[(&c,), ...]`).
+    /// Groups come in the order their prefixes first appear; within each group, combinations stay in breadth-first order.
+    #[inline]
+    #[must_use]
+    pub fn group_by_prefix<const K: usize>(&'item self) -> PrefixGroups<Tail::Advance, K>
+    where
+        Tail::Advance: SplitPrefix<K>,
+        <Tail::Advance as SplitPrefix<K>>::Prefix: Flatten,
+        <Tail::Advance as SplitPrefix<K>>::Suffix: Flatten,
+    {
+        let mut positions = HashMap::new();
+        let mut groups: PrefixGroups<Tail::Advance, K> = Vec::new();
+        while let Some(nested) = self.next_nested() {
+            let key = self
+                .tail
+                .indices()
+                .into_array()
+                .as_ref()
+                .iter()
+                .take(K)
+                .copied()
+                .collect::<Vec<_>>();
+            let (prefix, suffix) = nested.split_prefix();
+            let position = *positions.entry(key).or_insert_with(|| {
+                groups.push((prefix.flatten(), Vec::new()));
+                groups.len().saturating_sub(1)
+            });
+            if let Some(group) = groups.get_mut(position) {
+                group.1.push(suffix.flatten());
+            }
+        }
+        groups
+    }
+}
+
+/// Combinations grouped by their first `K` elements, as returned by `group_by_prefix`.
+pub type PrefixGroups<Advance, const K: usize> = Vec<(
+    <<Advance as SplitPrefix<K>>::Prefix as Flatten>::Flattened,
+    Vec<<<Advance as SplitPrefix<K>>::Suffix as Flatten>::Flattened>,
+)>;
@@ -0,0 +1,54 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Zip heterogeneous iterators into homogeneous `Vec`s of a shared tag type.
+
+use crate::{sealed, BreadthFirst, BreadthFirstManager};
+use alloc::vec::Vec;
+
+/// Convert one iterator's items into a tag shared by every iterator in a zip, e.g. a variant of a user-defined `enum`.
+pub trait IntoTagged<Tag> {
+    /// Tag representing this value.
+    #[must_use]
+    fn tagged(&self) -> Tag;
+}
+
+/// Collect a nested tuple of references like `(&A, (&B, (&C, ())))` into a `Vec` of tags.
+pub trait CollectTagged<Tag>: sealed::Nested {
+    /// Push each element's tag, from left to right.
+    fn collect_tagged(self, acc: &mut Vec<Tag>);
+}
+
+impl<Tag> CollectTagged<Tag> for () {
+    #[inline(always)]
+    fn collect_tagged(self, _: &mut Vec<Tag>) {}
+}
+
+impl<Tag, Head: IntoTagged<Tag>, Tail: CollectTagged<Tag>> CollectTagged<Tag> for (&Head, Tail) {
+    #[inline(always)]
+    fn collect_tagged(self, acc: &mut Vec<Tag>) {
+        acc.push(self.0.tagged());
+        self.1.collect_tagged(acc);
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Like `next` but with each element converted to a shared tag type and collected into a `Vec`.
+    #[inline]
+    #[must_use]
+    pub fn next_tagged<Tag>(&'item self) -> Option<Vec<Tag>>
+    where
+        Tail::Advance: CollectTagged<Tag>,
+    {
+        self.next_nested().map(|nested| {
+            let mut acc = Vec::with_capacity(Tail::DEPTH);
+            nested.collect_tagged(&mut acc);
+            acc
+        })
+    }
+}
@@ -0,0 +1,33 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Fixed-size arrays instead of tuples when every iterator yields the same type.
+
+use crate::{sealed, BreadthFirst, BreadthFirstManager};
+
+/// Convert a nested tuple like `(T, (T, (T, ())))` to an array like `[T; 3]`.
+pub trait IntoArray: sealed::Nested {
+    /// Array with one element per element of the nested tuple.
+    type Array;
+    /// Convert e.g. `(T, (T, (T, ())))` into `[T; 3]`.
+    #[must_use]
+    fn into_array(self) -> Self::Array;
+}
+
+breadth_first_zip_macros::implement_array!(); // Implement for (T, ()), (T, (T, ())), (T, (T, (T, ()))), ...
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+where
+    Tail::Advance: IntoArray,
+{
+    /// Like `next` but with the output as an array, e.g. `[&T; 3]` instead of `(&T, &T, &T)`.
+    #[inline]
+    #[must_use]
+    pub fn next_array(&'item self) -> Option<<Tail::Advance as IntoArray>::Array> {
+        self.next_nested().map(IntoArray::into_array)
+    }
+}
@@ -0,0 +1,406 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Combinations batched by index sum.
+
+use crate::{
+    diagnostic::ExactSizeBreadthFirst, element_cost, homogeneous::IntoArray, Ascending,
+    BreadthFirst, BreadthFirstManager, Flatten,
+};
+use alloc::{
+    vec,
+    vec::{IntoIter, Vec},
+};
+
+/// Iterator over batches of combinations sharing an index sum, each paired with that index sum.
+pub struct ByIndexSum<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>> {
+    /// Zip handing out references.
+    manager: &'item BreadthFirstManager<'item, Tail, Schedule>,
+    /// First combination of the next batch, already pulled while finishing the last one.
+    pending: Option<(usize, <Tail::Advance as Flatten>::Flattened)>,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>> Iterator
+    for ByIndexSum<'item, Tail, Schedule>
+{
+    type Item = (usize, Vec<<Tail::Advance as Flatten>::Flattened>);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index_sum, first) = self.pending.take().or_else(|| self.pull())?;
+        let mut batch = vec![first];
+        while let Some((next_index_sum, combination)) = self.pull() {
+            if next_index_sum != index_sum {
+                self.pending = Some((next_index_sum, combination));
+                break;
+            }
+            batch.push(combination);
+        }
+        Some((index_sum, batch))
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    ByIndexSum<'item, Tail, Schedule>
+{
+    /// Pull the next combination along with its index sum.
+    #[inline]
+    fn pull(&self) -> Option<(usize, <Tail::Advance as Flatten>::Flattened)> {
+        let combination = self.manager.next()?;
+        Some((self.manager.index_sum.get()?, combination))
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Batch the remaining combinations by index sum, yielding `(index_sum, combinations_with_that_index_sum)`.
+    /// Stops the first time `next` would return `None`, i.e. once we run out of combinations.
+    #[inline]
+    #[must_use]
+    pub const fn by_index_sum(&'item self) -> ByIndexSum<'item, Tail, Schedule> {
+        ByIndexSum {
+            manager: self,
+            pending: None,
+        }
+    }
+    /// Drain every remaining combination into a `Vec` whose `k`th element holds every combination with index sum `k`,
+    /// in the usual order (empty for any index sum with none, e.g. with weights).
+    #[inline]
+    #[must_use]
+    pub fn levels_vec(&'item self) -> Vec<Vec<<Tail::Advance as Flatten>::Flattened>> {
+        let mut levels = Vec::new();
+        for (index_sum, batch) in self.by_index_sum() {
+            if levels.len() <= index_sum {
+                levels.resize_with(index_sum.saturating_add(1), Vec::new);
+            }
+            if let Some(level) = levels.get_mut(index_sum) {
+                level.extend(batch);
+            }
+        }
+        levels
+    }
+    /// Drain every remaining combination into those satisfying `predicate` and those that don't, each in the usual order,
+    /// like `owned().partition(predicate)` but without cloning anything: both halves hold references into the cache.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::type_complexity)]
+    pub fn partition_combinations<
+        Predicate: FnMut(&<Tail::Advance as Flatten>::Flattened) -> bool,
+    >(
+        &'item self,
+        mut predicate: Predicate,
+    ) -> (
+        Vec<<Tail::Advance as Flatten>::Flattened>,
+        Vec<<Tail::Advance as Flatten>::Flattened>,
+    ) {
+        let (mut matching, mut rest) = (Vec::new(), Vec::new());
+        while let Some(combination) = self.next() {
+            if predicate(&combination) {
+                matching.push(combination);
+            } else {
+                rest.push(combination);
+            }
+        }
+        (matching, rest)
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>> BreadthFirstManager<'item, Tail, Ascending> {
+    /// Visit every combination with exactly `token`'s index sum, in the usual order, and return how many there were,
+    /// e.g. on a remote worker that rebuilt this zip from its own copy of the iterators (see `level_tokens`).
+    /// Jumps straight to that index sum without yielding any combination before it, then `reset`s this zip afterward.
+    #[inline]
+    pub fn process_level<F: FnMut(<Tail::Advance as Flatten>::Flattened)>(
+        &'item self,
+        token: LevelToken,
+        mut f: F,
+    ) -> usize {
+        self.index_sum.set(Some(token.index_sum));
+        self.tail.rewind();
+        let mut processed = 0_usize;
+        while let Some(combination) = self.tail.next(token.index_sum) {
+            f(combination.flatten());
+            processed = processed.saturating_add(1);
+        }
+        self.reset();
+        processed
+    }
+}
+
+/// Iterator over whole index sums of combinations, stopping before the first index sum with no combination satisfying a predicate.
+pub struct TakeWhileLevel<
+    'item,
+    Tail: BreadthFirst<'item>,
+    Schedule: Iterator<Item = usize>,
+    Predicate,
+> {
+    /// Batches of combinations sharing an index sum, or `None` once one failed the predicate.
+    levels: Option<ByIndexSum<'item, Tail, Schedule>>,
+    /// Rest of the current batch.
+    batch: IntoIter<<Tail::Advance as Flatten>::Flattened>,
+    /// Whether a combination is worth exploring past.
+    predicate: Predicate,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<
+        'item,
+        Tail: BreadthFirst<'item>,
+        Schedule: Iterator<Item = usize>,
+        Predicate: FnMut(&<Tail::Advance as Flatten>::Flattened) -> bool,
+    > Iterator for TakeWhileLevel<'item, Tail, Schedule, Predicate>
+{
+    type Item = <Tail::Advance as Flatten>::Flattened;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(combination) = self.batch.next() {
+                return Some(combination);
+            }
+            let (_, batch) = self.levels.as_mut()?.next()?;
+            if !batch.iter().any(&mut self.predicate) {
+                self.levels = None;
+                return None;
+            }
+            self.batch = batch.into_iter();
+        }
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Every remaining combination, one whole index sum at a time, until an index sum where no combination satisfies `predicate`,
+    /// e.g. for branch-and-bound when higher index sums only get worse. Unlike `take_while`, one failure doesn't stop anything:
+    /// each index sum with even one match comes out in full (matches or not), and only an index sum without any ends iteration.
+    /// Buffers a whole index sum at a time, so each batch takes memory.
+    #[inline]
+    #[must_use]
+    pub fn take_while_level<Predicate: FnMut(&<Tail::Advance as Flatten>::Flattened) -> bool>(
+        &'item self,
+        predicate: Predicate,
+    ) -> TakeWhileLevel<'item, Tail, Schedule, Predicate> {
+        TakeWhileLevel {
+            levels: Some(self.by_index_sum()),
+            batch: Vec::new().into_iter(),
+            predicate,
+        }
+    }
+}
+
+/// Iterator over every combination by index sum, as usual, but in a pseudorandom order within each index sum.
+pub struct ShuffledWithinLevel<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>> {
+    /// Batches of combinations sharing an index sum.
+    levels: ByIndexSum<'item, Tail, Schedule>,
+    /// Rest of the current batch, already shuffled, to pop off the end.
+    batch: Vec<<Tail::Advance as Flatten>::Flattened>,
+    /// State of the pseudorandom number generator (`SplitMix64`).
+    state: u64,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>> Iterator
+    for ShuffledWithinLevel<'item, Tail, Schedule>
+{
+    type Item = <Tail::Advance as Flatten>::Flattened;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(combination) = self.batch.pop() {
+                return Some(combination);
+            }
+            let (_, batch) = self.levels.next()?;
+            self.batch = batch;
+            shuffle(&mut self.batch, &mut self.state);
+        }
+    }
+}
+
+/// Fisher-Yates shuffle driven by `SplitMix64`, which is fast, tiny, and plenty for shuffling (though not for cryptography).
+#[allow(clippy::single_call_fn)] // Kept apart from `ShuffledWithinLevel::next` so the iteration reads on its own
+#[inline]
+fn shuffle<T>(slice: &mut [T], state: &mut u64) {
+    for index in (1..slice.len()).rev() {
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut random = *state;
+        random = (random ^ (random >> 30_u32)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        random = (random ^ (random >> 27_u32)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        random ^= random >> 31_u32;
+        let Ok(choices) = u64::try_from(index.saturating_add(1)) else {
+            continue;
+        };
+        let Some(other) = random
+            .checked_rem(choices)
+            .and_then(|other| usize::try_from(other).ok())
+        else {
+            continue;
+        };
+        slice.swap(index, other);
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Every remaining combination, still in nondecreasing index sum, but in a pseudorandom order within each index sum,
+    /// e.g. for a randomized search that still explores cheaper combinations first.
+    /// The same `seed` always gives the same order. Buffers a whole index sum at a time, so each batch takes memory.
+    #[inline]
+    #[must_use]
+    pub const fn shuffled_within_level(
+        &'item self,
+        seed: u64,
+    ) -> ShuffledWithinLevel<'item, Tail, Schedule> {
+        ShuffledWithinLevel {
+            levels: self.by_index_sum(),
+            batch: Vec::new(),
+            state: seed,
+        }
+    }
+}
+
+/// One index sum of a zip to hand to a worker (e.g. on another machine) with `process_level`, with nothing borrowed:
+/// any zip over the same iterators can process it.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct LevelToken {
+    /// Index sum to visit.
+    pub index_sum: usize,
+    /// How many combinations have this index sum, e.g. to check that a worker processed all of them.
+    pub count: usize,
+}
+
+impl<'item, Tail: ExactSizeBreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+where
+    Tail::Indices: IntoArray,
+    <Tail::Indices as IntoArray>::Array: AsRef<[usize]>,
+{
+    /// How many combinations have each index sum, from zero up to the highest, without iterating over any of them:
+    /// e.g. for a progress bar per index sum, or to skip whole index sums at once.
+    /// Counts come from convolving each iterator's kept length in turn with stride equal to its weight
+    /// (i.e. multiplying the polynomials `1 + x^w + ... + x^(w * (len - 1))`), so they account for weights, `prune`, and `truncate_axis`.
+    /// Index sums no combination has (possible with weights other than 1) get a count of zero.
+    ///
+    /// Finds kept lengths with `kept_lengths`, so only pruned iterators are pulled (and cached) up front; empty if any iterator has nothing kept.
+    #[inline]
+    #[must_use]
+    pub fn level_counts(&self) -> Vec<usize> {
+        let lengths = self.tail.kept_lengths().into_array();
+        let weights = self.tail.weights().into_array();
+        let mut counts = vec![1_usize];
+        for (&length, &weight) in lengths.as_ref().iter().zip(weights.as_ref()) {
+            let Some(longest) = length.checked_sub(1) else {
+                return Vec::new();
+            };
+            let mut convolved =
+                vec![0_usize; counts.len().saturating_add(weight.saturating_mul(longest))];
+            for rank in 0..length {
+                let Some(cost) = element_cost(weight, rank) else {
+                    break;
+                };
+                for (index_sum, &count) in counts.iter().enumerate() {
+                    if let Some(slot) = convolved.get_mut(index_sum.saturating_add(cost)) {
+                        *slot = slot.saturating_add(count);
+                    }
+                }
+            }
+            counts = convolved;
+        }
+        counts
+    }
+    /// One token per index sum, from zero up to the highest, together covering every combination exactly once:
+    /// dispatch each to a worker that rebuilds this zip and calls `process_level` on it. Same caveats as `level_counts`.
+    #[inline]
+    #[must_use]
+    pub fn level_tokens(&self) -> Vec<LevelToken> {
+        self.level_counts()
+            .into_iter()
+            .enumerate()
+            .map(|(index_sum, count)| LevelToken { index_sum, count })
+            .collect()
+    }
+    /// Index sum of the combination at this 0-based position in the usual order (counting from the very first combination),
+    /// or `None` if there aren't that many. Binary-searches the running total of `level_counts`, so it's as cheap as that.
+    #[inline]
+    #[must_use]
+    pub fn level_of_position(&self, position: usize) -> Option<usize> {
+        let ends: Vec<usize> = self
+            .level_counts()
+            .into_iter()
+            .scan(0_usize, |end, count| {
+                *end = end.saturating_add(count);
+                Some(*end)
+            })
+            .collect();
+        let level = ends.partition_point(|&end| end <= position);
+        (level < ends.len()).then_some(level)
+    }
+    /// 0-based position in the usual order of the first combination with this index sum, i.e. how many come before it,
+    /// or `None` if no combination has it. The inverse of `level_of_position`.
+    #[inline]
+    #[must_use]
+    pub fn first_position_of_level(&self, level: usize) -> Option<usize> {
+        let counts = self.level_counts();
+        counts.get(level)?;
+        Some(
+            counts
+                .get(..level)?
+                .iter()
+                .fold(0_usize, |sum, &count| sum.saturating_add(count)),
+        )
+    }
+}
+
+impl<'item, Tail: ExactSizeBreadthFirst<'item>> BreadthFirstManager<'item, Tail, Ascending>
+where
+    Tail::Indices: IntoArray,
+    <Tail::Indices as IntoArray>::Array: AsRef<[usize]>,
+{
+    /// Skip the next `n` combinations, like `Iterator::skip` on `owned` but without visiting most of them,
+    /// and return how many were skipped (fewer than `n` only if we ran out).
+    ///
+    /// Whole index sums are skipped at once using `level_counts`, so only the rest of the index sum we're in
+    /// and the start of the one we land in are stepped through (borrowed, never cloned). Same caveats as `level_counts`.
+    #[inline]
+    pub fn skip_combinations(&'item self, n: usize) -> usize {
+        let mut skipped = 0_usize;
+        // We don't know how far into the current index sum we are, so finish it one combination at a time
+        if let Some(current) = self.index_sum.get() {
+            while skipped < n {
+                let state = self.state();
+                let found = self.next_nested().is_some();
+                if self.schedule_over() {
+                    return skipped;
+                }
+                if !found || self.index_sum.get() != Some(current) {
+                    self.restore(state); // crossed into the next index sum, which we can skip wholesale instead
+                    break;
+                }
+                skipped = skipped.saturating_add(1);
+            }
+        }
+        let counts = self.level_counts();
+        let Some(mut level) = self.schedule.borrow().0 else {
+            return skipped;
+        };
+        while let Some(&count) = counts.get(level) {
+            let Some(total) = skipped.checked_add(count).filter(|&total| total <= n) else {
+                break;
+            };
+            skipped = total;
+            level = level.saturating_add(1);
+        }
+        self.index_sum.set(None);
+        *self.schedule.borrow_mut() = Ascending(Some(level));
+        self.schedule_over.set(false);
+        while skipped < n && self.next_nested().is_some() {
+            skipped = skipped.saturating_add(1);
+        }
+        skipped
+    }
+}
@@ -15,6 +15,9 @@
 //! - Repeat this process until we exhaust the first iterator.
 //!     - When you've done that, increase `i` and repeat.
 //! - Once `i` exceeds the longest iterator's length, we're done: return `None`.
+//!
+//! Each item is pulled from its iterator once, cached, and handed out by reference from then on:
+//! combinations borrow from the zip instead of cloning anything, so there's no need for e.g. `Cow`.
 
 #![cfg_attr(not(test), no_std)]
 #![deny(warnings)]
@@ -34,44 +37,127 @@
     clippy::expect_used,
     clippy::implicit_return,
     clippy::inline_always,
+    clippy::multiple_inherent_impl,
     clippy::needless_borrowed_reference,
     clippy::panic,
+    clippy::pub_use,
     clippy::question_mark_used,
     clippy::separated_literal_suffix,
     clippy::string_add,
     clippy::unwrap_used
 )]
 
-use ::core::{cell::Cell, convert::Infallible, marker::PhantomData};
-use reiterator::{Reiterate, Reiterator};
+#[cfg(any(feature = "alloc", not(feature = "external-reiterator")))]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+use ::core::{
+    cell::{Cell, RefCell},
+    convert::Infallible,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::StepBy,
+    marker::PhantomData,
+    ops::Range,
+};
+/// Lightweight `{ index, value }` pair that `Reiterator::get` returns, from the in-crate `reiterate` module.
+#[cfg(not(feature = "external-reiterator"))]
+pub use reiterate::{indexed, Indexed};
+/// Caching, rewindable iterator adapter that each zipped iterator gets wrapped in, from the in-crate `reiterate` module for standalone use.
+#[cfg(not(feature = "external-reiterator"))]
+pub use reiterate::{Reiterate, Reiterator};
+/// Lightweight `{ index, value }` pair that `Reiterator::get` returns, re-exported from the `reiterator` crate.
+#[cfg(feature = "external-reiterator")]
+pub use reiterator::{indexed, Indexed};
+/// Caching, rewindable iterator adapter that each zipped iterator gets wrapped in, re-exported from the `reiterator` crate for standalone use.
+#[cfg(feature = "external-reiterator")]
+pub use reiterator::{Reiterate, Reiterator};
+use sealed::Flatten;
+
+pub mod construction;
+pub mod cooperative;
+#[cfg(feature = "std")]
+pub mod dedup;
+pub mod dependent;
+pub mod diagnostic;
+pub mod fallible;
+pub mod flat;
+#[cfg(feature = "std")]
+pub mod grouping;
+#[cfg(feature = "alloc")]
+pub mod heterogeneous;
+pub mod homogeneous;
+#[cfg(feature = "alloc")]
+pub mod levels;
+pub mod monotonic;
+pub mod ordering;
+pub mod owned;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "std")]
+pub mod pretty;
+pub mod projection;
+#[cfg(not(feature = "external-reiterator"))]
+pub mod reiterate;
+pub mod sampling;
+pub mod streaming;
 #[cfg(test)]
 mod test;
-
-/// Flatten a nested tuple like `(A, (B, (C, ())))` to a flat one like `(A, B, C)`
-pub trait Flatten {
-    /// Flat tuple, e.g. `(A, B, C)`, not `(A, (B, (C, ())))`.
-    type Flattened;
-    /// Flatten e.g. `(A, (B, (C, ())))` into `(A, B, C)`.
-    #[must_use]
-    fn flatten(self) -> Self::Flattened;
-}
-
-impl Flatten for () {
-    type Flattened = Self;
-    #[inline(always)]
-    #[must_use]
-    fn flatten(self) -> Self::Flattened {}
-}
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod uniform;
+pub mod windows;
 
 breadth_first_zip_macros::implement_flatten!();
 
 /// End of a recursive implementation of a breadth-first exhaustive `zip`.
+/// On its own (i.e. zipping `()`), yields the empty combination `()` exactly once, like any empty product.
 #[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
 pub struct BaseCase(Cell<bool>);
 
+#[allow(clippy::missing_trait_methods)]
+impl Hash for BaseCase {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.get().hash(state);
+    }
+}
+
 /// Sealed traits.
 mod sealed {
+    /// Nested tuples like `(A, (B, (C, ())))`, i.e. either `()` or `(Head, Tail)` where `Tail` is also one.
+    pub trait Nested {}
+    impl Nested for () {}
+    impl<Head, Tail: Nested> Nested for (Head, Tail) {}
+    /// Flatten a nested tuple like `(A, (B, (C, ())))` to a flat one like `(A, B, C)`.
+    ///
+    /// Internal: only the nested tuples this crate builds implement it, and it can't be named outside this crate,
+    /// so every combination comes out already flat. Flat tuples can't also flatten to themselves,
+    /// since e.g. `(A, (B, ()))` is already a flat pair.
+    pub trait Flatten: Nested {
+        /// Flat tuple, e.g. `(A, B, C)`, not `(A, (B, (C, ())))`.
+        type Flattened;
+        /// Flatten e.g. `(A, (B, (C, ())))` into `(A, B, C)`.
+        #[must_use]
+        fn flatten(self) -> Self::Flattened;
+        /// Flatten, then convert into anything that implements `From` the flat tuple, e.g. a struct with a named field for each element.
+        #[inline(always)]
+        #[must_use]
+        fn flatten_into<T: From<Self::Flattened>>(self) -> T
+        where
+            Self: Sized,
+        {
+            T::from(self.flatten())
+        }
+    }
+    #[allow(clippy::missing_trait_methods)]
+    impl Flatten for () {
+        type Flattened = Self;
+        #[inline(always)]
+        #[must_use]
+        fn flatten(self) -> Self::Flattened {}
+    }
     /// Either `BaseCase` or a sequence `BreadthFirst<Whatever, ...>` ending in `BaseCase` on the right-hand side.
     pub trait BreadthFirst {}
     impl BreadthFirst for super::BaseCase {}
@@ -87,16 +173,52 @@ pub trait BreadthFirst<'item>: sealed::BreadthFirst {
     const DEPTH: usize;
     /// Output of `advance` if successful.
     type Advance: Flatten;
+    /// Current index into each iterator, as a nested tuple like `(usize, (usize, (usize, ())))`.
+    type Indices: Clone + fmt::Debug + Eq + Hash + Ord;
     /// Fallibly choose the next output.
     #[must_use]
     fn next(&'item self, index_sum: usize) -> Option<Self::Advance>;
-    /// Rewind the iterator back to its starting point
+    /// Rewind the iterator back to its starting point (idempotent: rewinding again right away changes nothing).
     fn rewind(&self);
+    /// Zero-based position (from here on) of the first empty iterator, if any.
+    #[must_use]
+    fn empty_axis(&self) -> Option<usize>;
+    /// Current index into each iterator.
+    #[must_use]
+    fn indices(&self) -> Self::Indices;
+    /// Set how much each step along each iterator adds to the index sum, from left to right.
+    fn set_weights(&mut self, weights: &[usize]);
+    /// Move each iterator to the position recorded by `indices`, as if it had just yielded the element there.
+    fn restore(&self, indices: Self::Indices);
+    /// Element at each of these indices (one per iterator, from left to right), leaving the current position untouched.
+    #[must_use]
+    fn at_indices(&'item self, indices: &[usize]) -> Option<Self::Advance>;
+    /// Last element of each iterator, leaving the current position untouched.
+    /// Walks each iterator to its end (but never the whole product), so this doesn't terminate on infinite iterators.
+    #[must_use]
+    fn last_of_each(&'item self) -> Option<Self::Advance>;
+    /// Weighted sum of the current position in each iterator (not counting pruned elements), i.e. the index sum of the current combination.
+    #[must_use]
+    fn index_sum(&self) -> usize;
+    /// Largest index sum of any combination, or `None` if we don't know yet (i.e. until each iterator has run out once).
+    #[must_use]
+    fn max_index_sum(&self) -> Option<usize>;
+    /// Consider only the first `max_len` kept elements of the `axis`th iterator (from here on).
+    fn truncate_axis(&mut self, axis: usize, max_len: usize);
+    /// Position of the first iterator that might never run out, judging by `size_hint` (and ignoring truncated iterators),
+    /// or `None` if every one of them has an upper bound.
+    #[must_use]
+    fn infinite_axis(&self) -> Option<usize>;
+    /// Whether we've seen some iterator turn out to have no kept elements at all (e.g. empty, or pruned or truncated to nothing),
+    /// in which case there are no combinations.
+    #[must_use]
+    fn no_combinations(&self) -> bool;
 }
 
 impl<'item> BreadthFirst<'item> for BaseCase {
     const DEPTH: usize = 0;
     type Advance = ();
+    type Indices = ();
     #[inline(always)]
     #[must_use]
     fn next(&self, index_sum: usize) -> Option<Self::Advance> {
@@ -108,6 +230,44 @@ impl<'item> BreadthFirst<'item> for BaseCase {
     fn rewind(&self) {
         self.0.set(true);
     }
+    #[inline(always)]
+    fn empty_axis(&self) -> Option<usize> {
+        None
+    }
+    #[inline(always)]
+    fn indices(&self) -> Self::Indices {}
+    #[inline(always)]
+    fn set_weights(&mut self, _: &[usize]) {}
+    #[inline(always)]
+    fn restore(&self, (): Self::Indices) {
+        self.0.set(false);
+    }
+    #[inline(always)]
+    fn at_indices(&self, indices: &[usize]) -> Option<Self::Advance> {
+        indices.is_empty().then_some(())
+    }
+    #[inline(always)]
+    fn last_of_each(&self) -> Option<Self::Advance> {
+        Some(())
+    }
+    #[inline(always)]
+    fn index_sum(&self) -> usize {
+        0
+    }
+    #[inline(always)]
+    fn max_index_sum(&self) -> Option<usize> {
+        Some(0)
+    }
+    #[inline(always)]
+    fn truncate_axis(&mut self, _: usize, _: usize) {}
+    #[inline(always)]
+    fn infinite_axis(&self) -> Option<usize> {
+        None
+    }
+    #[inline(always)]
+    fn no_combinations(&self) -> bool {
+        false
+    }
 }
 
 /// Recursive implementation of a breadth-first exhaustive `zip`.
@@ -116,20 +276,209 @@ pub struct BreadthFirstZipped<'item, Head: Iterator, Tail: BreadthFirst<'item>>
     iter: Reiterator<Head>,
     /// Implementations for the rest of the list.
     tail: Tail,
+    /// How much each step along this iterator adds to the index sum.
+    weight: usize,
+    /// Which elements of this iterator are worth exploring the rest of the list for, or `None` for all of them.
+    #[allow(clippy::type_complexity)]
+    keep: Option<&'item (dyn Fn(&Head::Item) -> bool + Sync)>,
+    /// How many kept elements precede the current one, i.e. its index as if pruned elements never existed.
+    rank: Cell<usize>,
+    /// How many kept elements this iterator has in total, or `None` until we've seen it run out.
+    len: Cell<Option<usize>>,
+    /// Most kept elements to consider from this iterator, or `None` for all of them.
+    max_len: Option<usize>,
+    /// `size_hint` of this iterator when it was zipped, e.g. exact for an `ExactSizeIterator` or with no upper bound for `0..`.
+    hint: (usize, Option<usize>),
     /// Representation of this struct's lifetime.
     lifetime: PhantomData<&'item Infallible>,
 }
 
+impl<'item, Head: Default + Iterator, Tail: BreadthFirst<'item> + Default> Default
+    for BreadthFirstZipped<'item, Head, Tail>
+{
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(Head::default(), Tail::default())
+    }
+}
+
 impl<'item, Head: Iterator, Tail: BreadthFirst<'item>> BreadthFirstZipped<'item, Head, Tail> {
     /// Initialize a new recursive node of a breadth-first zip implementation.
+    /// Lazy: nothing is pulled from `head` until the first call to `next`.
+    /// `head` needn't be `Clone`: each element is pulled exactly once, side effects and all, then cached.
     #[inline(always)]
     pub fn new(head: Head, tail: Tail) -> Self {
+        let hint = head.size_hint();
         Self {
             iter: head.reiterate(),
             tail,
+            weight: 1,
+            keep: None,
+            rank: Cell::new(0),
+            len: Cell::new(None),
+            max_len: None,
+            hint,
             lifetime: PhantomData,
         }
     }
+    /// Skip every element of this iterator for which `keep` returns `false`, without exploring the rest of the list at all for it,
+    /// e.g. for branch-and-bound, with `keep` capturing the bound (`Sync` so the zip stays `Send`: use an atomic for a bound that tightens). Skipped elements don't count toward index sums, just like `Iterator::filter`,
+    /// but indices (e.g. in `state` or `inspect_indices`) still count position in the original iterator.
+    /// Only affects iteration (`next` and friends), not random access like `at` or `last`; call it before the first `next`.
+    #[inline(always)]
+    pub fn prune(&mut self, keep: &'item (dyn Fn(&Head::Item) -> bool + Sync)) {
+        self.keep = Some(keep);
+        self.len = Cell::new(None);
+    }
+    /// Consider only the first `max_len` kept elements of this iterator, like `Iterator::take` but in place:
+    /// nothing past them is ever pulled. Lasts across `reset`, since it's part of this zip rather than of its progress.
+    /// Like `prune`, only affects iteration (`next` and friends), not random access like `at` or `last`;
+    /// call it before the first `next` (or `reset` afterward).
+    #[inline(always)]
+    pub const fn truncate(&mut self, max_len: usize) {
+        self.max_len = Some(max_len);
+        self.len = Cell::new(None);
+    }
+    /// Implementation for the rest of the list, e.g. to `prune` a later iterator.
+    #[inline(always)]
+    #[must_use]
+    pub const fn tail_mut(&mut self) -> &mut Tail {
+        &mut self.tail
+    }
+    /// Element at an arbitrary index, leaving the current position untouched.
+    #[inline(always)]
+    #[must_use]
+    fn at(&self, index: usize) -> Option<&Head::Item> {
+        element_at(&self.iter, index)
+    }
+    /// Remember that this iterator ran out right at the current rank, then return `None`.
+    #[inline(always)]
+    fn ran_out(&self) -> Option<()> {
+        self.len.set(Some(self.rank.get()));
+        None
+    }
+    /// Whether the current element is past the first `max_len` kept elements.
+    #[inline(always)]
+    fn truncated(&self) -> bool {
+        self.max_len
+            .is_some_and(|max_len| self.rank.get() >= max_len)
+    }
+    /// Move past any pruned elements, or return `None` if this iterator runs out first.
+    #[inline(always)]
+    fn skip_pruned(&self) -> Option<()> {
+        if self.truncated() {
+            return self.ran_out();
+        }
+        loop {
+            let Some(indexed) = self.iter.get() else {
+                return self.ran_out();
+            };
+            if self.keep.is_none_or(|keep| keep(indexed.value)) {
+                return Some(());
+            }
+            let _: Option<_> = self.iter.next();
+        }
+    }
+    /// Move to the next kept element, or return `None` if this iterator runs out first.
+    #[inline(always)]
+    fn advance(&self) -> Option<()> {
+        self.rank.set(self.rank.get().checked_add(1)?);
+        if self.truncated() || self.iter.next().is_none() {
+            return self.ran_out();
+        }
+        self.skip_pruned()
+    }
+    /// Choose the next output, given a way to choose the next output of `tail` for a given index sum.
+    #[inline(always)]
+    #[must_use]
+    fn next_with<'borrow, T>(
+        &'borrow self,
+        index_sum: usize,
+        tail_next: impl Fn(&'borrow Tail, usize) -> Option<T>,
+    ) -> Option<(&'borrow Head::Item, T)> {
+        if self.truncated() || self.len.get() == Some(0) {
+            return None;
+        }
+        walk(
+            index_sum,
+            self.weight,
+            || element_cost(self.weight, self.rank.get()),
+            |remaining| {
+                // Once every later iterator has run out, we know the most they can add up to, so we can skip exploring them for any more than that
+                if self.tail.max_index_sum().is_some_and(|max| remaining > max) {
+                    return None;
+                }
+                let tail = tail_next(&self.tail, remaining)?;
+                self.iter.get().map(|indexed| (indexed.value, tail))
+            },
+            || self.advance(),
+            || self.tail.rewind(),
+        )
+    }
+}
+
+/// Step through one iterator until the rest of the list has a combination with whatever's left of `index_sum`,
+/// the loop at the heart of every breadth-first zip here, however the rest of the list is represented.
+/// `cost` is what the current element adds to the index sum (each later one adding `weight` more), `rest` tries the rest of the list
+/// with what's left, `advance` moves this iterator to its next element, and `rewind` starts the rest of the list over for it.
+#[inline(always)]
+fn walk<T>(
+    index_sum: usize,
+    weight: usize,
+    cost: impl Fn() -> Option<usize>,
+    mut rest: impl FnMut(usize) -> Option<T>,
+    advance: impl Fn() -> Option<()>,
+    rewind: impl Fn(),
+) -> Option<T> {
+    loop {
+        let spent = cost()?;
+        if let Some(found) = rest(remaining_budget(index_sum, spent)?) {
+            return Some(found);
+        }
+        next_fits(spent, weight, index_sum).then(&advance)??; // Comparison never changes the output (`remaining_budget` would bail next time around) but keeps us from pulling an element we don't need yet
+        rewind();
+    }
+}
+
+/// Element of a caching iterator at an arbitrary index, leaving its current position untouched.
+#[inline(always)]
+#[must_use]
+fn element_at<Iter: Iterator>(iter: &Reiterator<Iter>, index: usize) -> Option<&Iter::Item> {
+    let current = iter.index.replace(index);
+    let value = iter.get().map(|indexed| indexed.value);
+    iter.index.set(current);
+    value
+}
+
+/// How much the element at `rank` (counting only kept elements) adds to the index sum,
+/// or `None` if that overflows, in which case it can't fit in any index sum: this index sum is done with this iterator.
+#[inline(always)]
+#[must_use]
+const fn element_cost(weight: usize, rank: usize) -> Option<usize> {
+    weight.checked_mul(rank)
+}
+
+/// Index sum left for the rest of the list after spending `cost` on this iterator,
+/// or `None` if `cost` alone exceeds `index_sum`, in which case neither this element nor any later (costlier) one fits:
+/// this index sum is done with this iterator, not just this element.
+/// Only `walk` calls it, but naming it there keeps that `?` from reading as a plain arithmetic guard.
+#[allow(clippy::single_call_fn)]
+#[inline(always)]
+#[must_use]
+const fn remaining_budget(index_sum: usize, cost: usize) -> Option<usize> {
+    index_sum.checked_sub(cost)
+}
+
+/// Whether the element after one costing `cost` (i.e. costing `weight` more) could still fit in `index_sum`,
+/// `false` (done, like `remaining_budget`) if its cost would overflow, so `walk` stops before pulling an element that can't fit.
+#[allow(clippy::single_call_fn)]
+#[inline(always)]
+#[must_use]
+const fn next_fits(cost: usize, weight: usize, index_sum: usize) -> bool {
+    match cost.checked_add(weight) {
+        Some(next_cost) => next_cost <= index_sum,
+        None => false,
+    }
 }
 
 impl<'item, Head: Iterator, Tail: BreadthFirst<'item>> BreadthFirst<'item>
@@ -140,34 +489,133 @@ where
 {
     const DEPTH: usize = Tail::DEPTH + 1;
     type Advance = (&'item Head::Item, Tail::Advance);
+    type Indices = (usize, Tail::Indices);
     #[inline(always)]
     #[must_use]
     fn next(&'item self, index_sum: usize) -> Option<Self::Advance> {
-        loop {
-            if let Some(tail) = self
-                .tail
-                .next(index_sum.checked_sub(self.iter.index.get())?)
-            {
-                return self.iter.get().map(|indexed| (indexed.value, tail));
-            }
-            (self.iter.index.get() < index_sum).then(|| self.iter.next())??; // Comparison is just an optimization, not logically necessary
-            self.tail.rewind();
-        }
+        self.next_with(index_sum, Tail::next)
     }
     #[inline(always)]
     fn rewind(&self) {
         self.iter.restart();
+        self.rank.set(0);
+        let _: Option<()> = self.skip_pruned();
         self.tail.rewind();
     }
+    #[inline(always)]
+    fn empty_axis(&self) -> Option<usize> {
+        if self.iter.get().is_none() {
+            return Some(0);
+        }
+        self.tail.empty_axis()?.checked_add(1)
+    }
+    #[inline(always)]
+    fn indices(&self) -> Self::Indices {
+        (self.iter.index.get(), self.tail.indices())
+    }
+    #[inline(always)]
+    fn set_weights(&mut self, weights: &[usize]) {
+        if let Some((&weight, rest)) = weights.split_first() {
+            self.weight = weight;
+            self.tail.set_weights(rest);
+        }
+    }
+    #[inline(always)]
+    fn restore(&self, (index, indices): Self::Indices) {
+        self.iter.index.set(index);
+        self.rank.set(self.keep.map_or(index, |keep| {
+            (0..index)
+                .filter(|&earlier| self.at(earlier).is_some_and(keep))
+                .count()
+        }));
+        self.tail.restore(indices);
+    }
+    #[inline(always)]
+    fn at_indices(&'item self, indices: &[usize]) -> Option<Self::Advance> {
+        let (&index, rest) = indices.split_first()?;
+        Some((self.at(index)?, self.tail.at_indices(rest)?))
+    }
+    #[inline(always)]
+    fn last_of_each(&'item self) -> Option<Self::Advance> {
+        Some((
+            (0..).map_while(|index| self.at(index)).last()?,
+            self.tail.last_of_each()?,
+        ))
+    }
+    #[inline(always)]
+    fn index_sum(&self) -> usize {
+        self.weight
+            .saturating_mul(self.rank.get())
+            .saturating_add(self.tail.index_sum())
+    }
+    #[inline(always)]
+    fn max_index_sum(&self) -> Option<usize> {
+        let last = self.len.get()?.checked_sub(1)?;
+        element_cost(self.weight, last)?.checked_add(self.tail.max_index_sum()?)
+    }
+    #[inline(always)]
+    fn truncate_axis(&mut self, axis: usize, max_len: usize) {
+        match axis.checked_sub(1) {
+            None => self.truncate(max_len),
+            Some(later) => self.tail.truncate_axis(later, max_len),
+        }
+    }
+    #[inline(always)]
+    fn infinite_axis(&self) -> Option<usize> {
+        if self.hint.1.is_none() && self.max_len.is_none() {
+            return Some(0);
+        }
+        self.tail.infinite_axis()?.checked_add(1)
+    }
+    #[inline(always)]
+    fn no_combinations(&self) -> bool {
+        self.len.get() == Some(0) || self.tail.no_combinations()
+    }
+}
+
+/// Default schedule for a breadth-first zip: every index sum, in increasing order.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Ascending(Option<usize>);
+
+impl Default for Ascending {
+    #[inline(always)]
+    fn default() -> Self {
+        Self(Some(0))
+    }
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl Iterator for Ascending {
+    type Item = usize;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index_sum = self.0?;
+        self.0 = index_sum.checked_add(1); // `None` once we've visited `usize::MAX`, i.e. the schedule is over (see `schedule_over`)
+        Some(index_sum)
+    }
 }
 
 /// Helper struct for a breadth-first zip: a counter controlling the maximum index sum of the internal recursive implementation.
+///
+/// A `Default` manager zips default iterators, which for anything like `Range` or `Empty` are empty, so it yields nothing.
+///
+/// `Send` whenever each iterator and its elements are, so it can be handed off to another thread, but never `Sync`:
+/// `next` takes `&self` and moves each iterator through `Cell`s (that's what lets combinations borrow from the zip),
+/// so sharing one between threads would race. To split the work instead, see `par_by_axis0` (with the `rayon` feature).
 #[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
-pub struct BreadthFirstManager<'item, Tail: BreadthFirst<'item>> {
+pub struct BreadthFirstManager<
+    'item,
+    Tail: BreadthFirst<'item>,
+    Schedule: Iterator<Item = usize> = Ascending,
+> {
     /// Recursive implementation.
     tail: Tail,
-    /// "Global" counter to allow the maximum possible sum of indices.
-    index_sum: Cell<usize>,
+    /// "Global" counter to allow the maximum possible sum of indices, or `None` if we need to pull the next one from `schedule`.
+    index_sum: Cell<Option<usize>>,
+    /// Sequence of index sums to visit, one after another.
+    schedule: RefCell<Schedule>,
+    /// Whether `schedule` has run out of index sums.
+    schedule_over: Cell<bool>,
     /// Representation of this struct's lifetime.
     lifetime: PhantomData<&'item Infallible>,
 }
@@ -179,42 +627,466 @@ impl<'item, Tail: BreadthFirst<'item>> BreadthFirstManager<'item, Tail> {
     pub const fn new(tail: Tail) -> Self {
         Self {
             tail,
-            index_sum: Cell::new(0),
+            index_sum: Cell::new(None),
+            schedule: RefCell::new(Ascending(Some(0))),
+            schedule_over: Cell::new(false),
+            lifetime: PhantomData,
+        }
+    }
+    /// Final combination, i.e. the last element of each iterator, without iterating over any others or moving this zip.
+    /// Walks each iterator to its end (but never the whole product), so this doesn't terminate on infinite iterators.
+    #[inline]
+    #[must_use]
+    pub fn last(&'item self) -> Option<<Tail::Advance as Flatten>::Flattened> {
+        self.tail.last_of_each().map(Flatten::flatten)
+    }
+    /// Start over from the first combination, as if freshly built.
+    /// Nothing is pulled from any iterator again: everything seen so far is cached.
+    /// That includes where each iterator ended, so an iterator that would have grown in the meantime (e.g. reading from a channel)
+    /// won't grow here: to explore the bigger space, zip the bigger iterators again instead.
+    #[inline]
+    pub fn reset(&self) {
+        self.index_sum.set(None);
+        *self.schedule.borrow_mut() = Ascending(Some(0));
+        self.schedule_over.set(false);
+    }
+    /// Pick up where another zip left off, given its `state` (e.g. after dropping the original iterators).
+    /// Only meaningful if `tail` has the same iterators (yielding the same elements) as the zip `state` came from.
+    /// Elements before the recorded position are pulled again as needed, but no combinations before it are yielded.
+    #[inline]
+    #[must_use]
+    pub fn from_state(tail: Tail, state: State<Tail::Indices>) -> Self {
+        let resumed = Self::new(tail);
+        resumed.restore(state);
+        resumed
+    }
+    /// Move back (or forward) to a `state` this zip was in before, as if it had just yielded the combination there.
+    #[inline]
+    pub(crate) fn restore(&self, state: State<Tail::Indices>) {
+        let Some(index_sum) = state.index_sum else {
+            return self.reset();
+        };
+        self.tail.restore(state.indices);
+        self.index_sum.set(Some(index_sum));
+        *self.schedule.borrow_mut() = Ascending(index_sum.checked_add(1));
+        self.schedule_over.set(false);
+    }
+    /// Collect at most `limit` of the remaining combinations, along with whether that's all of them,
+    /// i.e. whether we ran out before (or exactly at) the limit rather than cutting the search short.
+    /// Checking for the latter peeks one combination further, then moves back, so nothing is skipped on the next call to `next`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn collect_bounded(
+        &'item self,
+        limit: usize,
+    ) -> (
+        ::alloc::vec::Vec<<Tail::Advance as Flatten>::Flattened>,
+        bool,
+    ) {
+        let mut collected = ::alloc::vec::Vec::new();
+        while collected.len() < limit {
+            let Some(combination) = self.next() else {
+                return (collected, true);
+            };
+            collected.push(combination);
+        }
+        let state = self.state();
+        let exhausted = self.next_nested().is_none();
+        if !exhausted {
+            self.restore(state);
+        }
+        (collected, exhausted)
+    }
+    /// Initialize a new breadth-first algorithm in which each step along the `i`th iterator adds `weights[i]` to the index sum instead of 1,
+    /// so iterators with smaller weights are explored faster.
+    /// Combinations come out in order of `weights[0] * index[0] + weights[1] * index[1] + ...`.
+    /// # Panics
+    /// If `N` isn't the number of iterators.
+    #[inline]
+    #[must_use]
+    pub fn with_weights<const N: usize>(mut tail: Tail, weights: [usize; N]) -> Self {
+        assert_eq!(
+            N,
+            Tail::DEPTH,
+            "Expected one weight per iterator ({} iterators but {N} weights)",
+            Tail::DEPTH,
+        );
+        tail.set_weights(&weights);
+        Self::new(tail)
+    }
+    /// Initialize a new breadth-first algorithm visiting only every `step`th index sum (0, `step`, `2 * step`, ...),
+    /// e.g. to sample every other level of a huge search space.
+    /// Combinations at skipped index sums are never yielded.
+    /// # Panics
+    /// If `step` is zero.
+    #[inline]
+    #[must_use]
+    pub fn with_index_sum_step(
+        tail: Tail,
+        step: usize,
+    ) -> BreadthFirstManager<'item, Tail, StepBy<Ascending>> {
+        BreadthFirstManager::with_schedule(tail, Ascending(Some(0)).step_by(step))
+    }
+    /// Same zip, but visiting only index sums `0..n` (i.e. the first `n` levels), then stopping for good.
+    /// Keeps any progress so far, and since the result is still a zip, every other adapter works on it as usual.
+    #[inline]
+    #[must_use]
+    pub fn first_n_levels(self, n: usize) -> BreadthFirstManager<'item, Tail, Range<usize>> {
+        BreadthFirstManager {
+            tail: self.tail,
+            index_sum: Cell::new(self.index_sum.get().filter(|&index_sum| index_sum < n)),
+            schedule: RefCell::new(self.schedule.into_inner().0.unwrap_or(n)..n),
+            schedule_over: self.schedule_over,
+            lifetime: PhantomData,
+        }
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Initialize a new breadth-first algorithm visiting index sums in the order `schedule` yields them.
+    /// Within each index sum, combinations come out in the usual order; when `schedule` ends, so does iteration.
+    /// Nothing checks that `schedule` is increasing, so a non-monotonic schedule may revisit or skip index sums.
+    /// Scheduled index sums with no combinations are passed over, so `next` returns `None` only when `schedule` ends
+    /// or no combination is left past the index sum it just tried (for a decreasing schedule, later calls may still find some).
+    #[inline(always)]
+    #[must_use]
+    pub const fn with_schedule(tail: Tail, schedule: Schedule) -> Self {
+        Self {
+            tail,
+            index_sum: Cell::new(None),
+            schedule: RefCell::new(schedule),
+            schedule_over: Cell::new(false),
             lifetime: PhantomData,
         }
     }
+    /// Consider only the first `max_len` elements of the `I`th iterator, like `take(max_len)` on it but in place:
+    /// nothing past them is ever pulled (or cached). Lasts across `reset`, which starts over within the truncated space;
+    /// call it before the first `next` (or `reset` afterward).
+    #[inline]
+    pub fn truncate_axis<const I: usize>(&mut self, max_len: usize) {
+        const { assert!(I < Tail::DEPTH, "Axis out of range") };
+        self.tail.truncate_axis(I, max_len);
+    }
+    /// Whether any iterator might never run out (judging by `size_hint`), in which case neither will this zip:
+    /// it'll keep visiting higher index sums forever instead of returning `None` once it's seen every combination.
+    /// A heuristic: an iterator with no upper bound might still end, e.g. a `filter` on an infinite one can't know it will.
+    #[inline]
+    #[must_use]
+    pub fn has_infinite_axis(&self) -> bool {
+        self.tail.infinite_axis().is_some()
+    }
+    /// Check that this zip will terminate, i.e. that every iterator has an upper bound on its length (see `has_infinite_axis`).
+    /// # Errors
+    /// If any iterator might never run out, reporting the position of the first one that might.
+    #[inline]
+    pub fn require_finite(self) -> Result<Self, InfiniteAxis> {
+        self.tail
+            .infinite_axis()
+            .map_or(Ok(self), |index| Err(InfiniteAxis { index }))
+    }
+    /// Recursive implementation, e.g. to `prune` an iterator before the first call to `next`.
+    #[inline(always)]
+    #[must_use]
+    pub const fn tail_mut(&mut self) -> &mut Tail {
+        &mut self.tail
+    }
+    /// Snapshot of this zip's progress, e.g. to use as a key for memoization
+    /// or to resume later with `from_state` (it's just a handful of counters, so it's cheap to keep around).
+    #[inline]
+    #[must_use]
+    pub fn state(&self) -> State<Tail::Indices> {
+        State {
+            index_sum: self.index_sum.get(),
+            indices: self.tail.indices(),
+        }
+    }
+    /// Whether `next` returned `None` because the schedule ran out of index sums rather than because every combination was visited,
+    /// e.g. when zipping infinite iterators past an index sum of `usize::MAX`.
+    #[inline]
+    #[must_use]
+    pub const fn schedule_over(&self) -> bool {
+        self.schedule_over.get()
+    }
+    /// Combination at these indices (one per iterator, from left to right) without iterating or moving this zip,
+    /// as if this were a lazy N-dimensional array, or `None` if any index is out of range or there are too few or too many.
+    #[inline]
+    #[must_use]
+    pub fn at(&'item self, indices: &[usize]) -> Option<<Tail::Advance as Flatten>::Flattened> {
+        self.tail.at_indices(indices).map(Flatten::flatten)
+    }
+    /// Whether both zips have the same combinations left, in the same order.
+    /// Advances both until they differ or both run out, so e.g. `reset` afterward to iterate again.
+    #[inline]
+    #[must_use]
+    pub fn eq_remaining<
+        'other,
+        OtherTail: BreadthFirst<'other>,
+        OtherSchedule: Iterator<Item = usize>,
+    >(
+        &'item self,
+        other: &'other BreadthFirstManager<'other, OtherTail, OtherSchedule>,
+    ) -> bool
+    where
+        <Tail::Advance as Flatten>::Flattened:
+            PartialEq<<OtherTail::Advance as Flatten>::Flattened>,
+    {
+        ::core::iter::from_fn(|| self.next()).eq(::core::iter::from_fn(|| other.next()))
+    }
     /// Like `Iterator::next` but with a generic lifetime.
     /// Why not implement `Iterator`? <https://stackoverflow.com/questions/68606470/how-to-return-a-reference-when-implementing-an-iterator>
-    #[allow(clippy::should_implement_trait)]
+    /// For borrows that end before the next call, see `streaming::LendingBreadthFirst`.
+    #[allow(clippy::same_name_method, clippy::should_implement_trait)]
     #[inline(always)]
     #[must_use]
     pub fn next(&'item self) -> Option<<Tail::Advance as Flatten>::Flattened> {
-        self.tail
-            .next(self.index_sum.get())
-            .map_or_else(
-                || {
-                    self.index_sum.set(self.index_sum.get().checked_add(1)?);
-                    self.tail.rewind();
-                    self.tail.next(self.index_sum.get())
-                },
-                Some,
-            )
-            .map(Flatten::flatten)
+        self.next_nested().map(Flatten::flatten)
+    }
+    /// First remaining combination satisfying `predicate`, leaving this zip right after it.
+    /// Like `Iterator::find` on `owned`, but nothing is cloned: combinations that don't match are only ever borrowed.
+    #[inline]
+    #[must_use]
+    pub fn find_combination<Predicate: FnMut(&<Tail::Advance as Flatten>::Flattened) -> bool>(
+        &'item self,
+        mut predicate: Predicate,
+    ) -> Option<<Tail::Advance as Flatten>::Flattened> {
+        loop {
+            let combination = self.next()?;
+            if predicate(&combination) {
+                return Some(combination);
+            }
+        }
     }
+    /// Map every remaining combination through `f` and collect the results, e.g. into a `BTreeSet`.
+    /// Like `owned().map(f).collect()`, but `f` gets the borrowed combination, so nothing is cloned unless `f` does it.
+    #[inline]
+    #[must_use]
+    pub fn map_collect<
+        Output,
+        Collection: FromIterator<Output>,
+        F: FnMut(<Tail::Advance as Flatten>::Flattened) -> Output,
+    >(
+        &'item self,
+        f: F,
+    ) -> Collection {
+        ::core::iter::from_fn(|| self.next()).map(f).collect()
+    }
+    /// Like `next` but without flattening the output.
+    #[inline(always)]
+    #[must_use]
+    fn next_nested(&'item self) -> Option<Tail::Advance> {
+        self.next_with(Tail::next)
+    }
+    /// Choose the next output, given a way to choose the next output of `tail` for a given index sum.
+    #[inline(always)]
+    #[must_use]
+    fn next_with<'borrow, T>(
+        &'borrow self,
+        tail_next: impl Fn(&'borrow Tail, usize) -> Option<T>,
+    ) -> Option<T> {
+        if let Some(tail) = self
+            .index_sum
+            .get()
+            .and_then(|index_sum| tail_next(&self.tail, index_sum))
+        {
+            return Some(self.validate_ordering(tail));
+        }
+        loop {
+            let scheduled = self.schedule.borrow_mut().next();
+            self.index_sum.set(scheduled);
+            self.schedule_over.set(scheduled.is_none());
+            self.tail.rewind();
+            let index_sum = scheduled?;
+            if let Some(tail) = tail_next(&self.tail, index_sum) {
+                return Some(self.validate_ordering(tail));
+            }
+            if self.exhausted_past(index_sum) {
+                return None;
+            }
+        }
+    }
+    /// Whether no combination has an index sum above `index_sum`, so an empty `index_sum` means we've seen them all
+    /// rather than just a gap between weights. We can't tell until each iterator has run out once, so until then we keep looking.
+    #[inline(always)]
+    #[must_use]
+    fn exhausted_past(&self, index_sum: usize) -> bool {
+        self.tail.no_combinations()
+            || self
+                .tail
+                .max_index_sum()
+                .is_some_and(|max| index_sum >= max)
+    }
+    /// In debug builds, check that the combination we just chose really has the index sum we're visiting,
+    /// so that (with the default schedule) index sums never decrease. Catches bugs in each iterator's `advance` logic.
+    #[inline(always)]
+    fn validate_ordering<T>(&self, tail: T) -> T {
+        debug_assert_eq!(
+            Some(self.tail.index_sum()),
+            self.index_sum.get(),
+            "Breadth-first zip chose a combination outside the index sum it was visiting"
+        );
+        tail
+    }
+}
+
+/// Snapshot of a breadth-first zip's progress, omitting the iterators themselves (which can't be hashed).
+/// Right after a call to `next`, this pins down exactly which combination was just returned.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct State<Indices> {
+    /// Index sum currently being visited, or `None` before the first call to `next` (or once the schedule is over).
+    pub index_sum: Option<usize>,
+    /// Current index into each iterator, as a nested tuple like `(usize, (usize, (usize, ())))`.
+    pub indices: Indices,
 }
 
+/// Error constructing a breadth-first zip: one of the iterators was empty.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct EmptyAxis {
+    /// Zero-based position of the first empty iterator in the tuple.
+    pub index: usize,
+}
+
+impl fmt::Display for EmptyAxis {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Tried to breadth-first zip an empty iterator (at position {})",
+            self.index
+        )
+    }
+}
+
+/// Error from `require_finite`: one of the iterators might never run out, so neither would the zip.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct InfiniteAxis {
+    /// Zero-based position of the first iterator without an upper bound in the tuple.
+    pub index: usize,
+}
+
+impl fmt::Display for InfiniteAxis {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Tried to breadth-first zip an iterator that might never end (at position {})",
+            self.index
+        )
+    }
+}
+
+/// Error annotated with which step failed, e.g. which of several zips in a pipeline had an empty iterator (see `Label`).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct Labeled<Cause> {
+    /// What we were doing when the error happened.
+    pub label: &'static str,
+    /// Error itself.
+    pub cause: Cause,
+}
+
+impl<Cause: fmt::Display> fmt::Display for Labeled<Cause> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.label, self.cause)
+    }
+}
+
+/// Annotate an error from this crate (i.e. from `try_breadth_first` or `require_finite`) with which step failed.
+///
+/// Not called `context` so it never clashes with error-handling crates' extension traits when both are in scope.
+pub trait Label<T, Cause> {
+    /// Label the error, if any, with `label`, which then prefixes its message.
+    /// # Errors
+    /// If `self` is an error, with the same cause.
+    fn label(self, label: &'static str) -> Result<T, Labeled<Cause>>;
+}
+
+impl<T> Label<T, EmptyAxis> for Result<T, EmptyAxis> {
+    #[inline]
+    fn label(self, label: &'static str) -> Result<T, Labeled<EmptyAxis>> {
+        self.map_err(|cause| Labeled { label, cause })
+    }
+}
+
+impl<T> Label<T, InfiniteAxis> for Result<T, InfiniteAxis> {
+    #[inline]
+    fn label(self, label: &'static str) -> Result<T, Labeled<InfiniteAxis>> {
+        self.map_err(|cause| Labeled { label, cause })
+    }
+}
+
+/// Breadth-first zip over a tuple of iterators like `(A, B, C)`, without spelling out its nested implementation,
+/// e.g. to store one in a struct field.
+pub type BreadthFirstZipIter<'item, Iters> =
+    BreadthFirstManager<'item, <Iters as BreadthFirstZip<'item>>::Nested>;
+
+/// Two breadth-first zips over the same iterators, the first visiting index sums below some level and the second the rest.
+pub type SplitAtLevel<'item, Nested> = (
+    BreadthFirstManager<'item, Nested, Range<usize>>,
+    BreadthFirstManager<'item, Nested>,
+);
+
 /// Zip a tuple into a lazy breadth-first traversal of each possible combination with a monotonically increasing sum of indices.
+///
+/// The only bound on each element of the tuple is `IntoIterator`: no `Clone`, since each element is cached the first time it's pulled,
+/// so adapters like `map` and `filter` work as axes even when their closures capture something that isn't `Clone`,
+/// and collections like `Vec` can be zipped by value without calling `into_iter` on each one first.
+/// To hold one position fixed, use `core::iter::once(value)`: plain values aren't accepted directly,
+/// since telling them apart from iterators would take impls that overlap as far as the compiler is concerned.
 pub trait BreadthFirstZip<'item> {
     /// Rearrangement of input into a nested tuple.
     type Nested: BreadthFirst<'item>;
     /// Lazy breadth-first exhaustive `zip` that guarantees a monotonically increasing sum of indices.
+    /// Never fails: if any iterator is empty, so is the product, and `next` just returns `None` (see `try_breadth_first` to check).
     fn breadth_first(self) -> BreadthFirstManager<'item, Self::Nested>;
-    /// Unflatten a tuple like `(A, B, C)` to `BreadthFirstZipped<A, BreadthFirstZipped<B, BreadthFirstZipped<C, BaseCase>>>`.
+    /// Like `breadth_first`, but check up front that no iterator is empty.
     /// # Errors
-    /// If any iterator is empty.
+    /// If any iterator is empty, reporting the position of the first one that is.
+    #[inline]
+    fn try_breadth_first(self) -> Result<BreadthFirstManager<'item, Self::Nested>, EmptyAxis>
+    where
+        Self: Sized,
+    {
+        let nested = self.unflatten();
+        nested.empty_axis().map_or_else(
+            || Ok(BreadthFirstManager::new(nested)),
+            |index| Err(EmptyAxis { index }),
+        )
+    }
+    /// Like `breadth_first`, but cloning each iterator instead of consuming the tuple, like `slice::iter` borrows.
+    #[inline]
+    #[must_use]
+    fn breadth_first_ref(&self) -> BreadthFirstManager<'item, Self::Nested>
+    where
+        Self: Clone,
+    {
+        self.clone().breadth_first()
+    }
+    /// Split into two independent zips, e.g. to hand each to its own thread without `rayon`:
+    /// the first visits index sums `0..level` and the second `level..`, so together they visit every combination exactly once.
+    /// Each gets its own clone of every iterator (and caches its elements separately).
+    #[inline]
+    #[must_use]
+    fn split_at_level(self, level: usize) -> SplitAtLevel<'item, Self::Nested>
+    where
+        Self: Clone + Sized,
+    {
+        (
+            BreadthFirstManager::with_schedule(self.clone().unflatten(), 0..level),
+            BreadthFirstManager::with_schedule(self.unflatten(), Ascending(Some(level))),
+        )
+    }
+    /// Unflatten a tuple like `(A, B, C)` to `BreadthFirstZipped<A, BreadthFirstZipped<B, BreadthFirstZipped<C, BaseCase>>>`.
     fn unflatten(self) -> Self::Nested;
 }
 
+#[allow(clippy::missing_trait_methods)]
 impl<'item> BreadthFirstZip<'item> for () {
     type Nested = BaseCase;
     #[inline(always)]
@@ -228,3 +1100,5 @@ impl<'item> BreadthFirstZip<'item> for () {
 }
 
 breadth_first_zip_macros::implement!(); // Implement traits for (A,), (A, B), (A, B, C), (A, B, C, D), ...
+
+breadth_first_zip_macros::implement_zip_n!(); // Define breadth_first_zip1(a), breadth_first_zip2(a, b), ...
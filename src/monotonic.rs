@@ -0,0 +1,211 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Shortcuts for iterators known to yield their items in ascending order.
+
+use crate::{
+    homogeneous::IntoArray, BaseCase, BreadthFirst, BreadthFirstManager, BreadthFirstZipped,
+    Flatten,
+};
+use ::core::ops::{Range, RangeInclusive};
+
+/// Marker for iterators whose items come out in ascending order.
+/// Implementing this for an iterator that isn't sorted gives wrong answers (but is never undefined behavior).
+pub trait Sorted: Iterator {}
+
+impl<A> Sorted for Range<A> where Self: Iterator {}
+impl<A> Sorted for RangeInclusive<A> where Self: Iterator {}
+
+/// Promise about how a key changes as any one index into a combination increases.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum Monotonic {
+    /// Never decreases, e.g. the sum of the elements.
+    Increasing,
+    /// Never increases, e.g. the negated sum of the elements.
+    Decreasing,
+}
+
+/// Either `BaseCase` or a sequence of `BreadthFirstZipped` over `Sorted` iterators ending in `BaseCase`.
+pub trait SortedBreadthFirst<'item>: BreadthFirst<'item> {
+    /// First element of each iterator, i.e. the smallest.
+    #[must_use]
+    fn first(&'item self) -> Option<Self::Advance>;
+    /// Last element of each iterator, i.e. the largest.
+    /// Walks each iterator to its end (but never the whole product), so this doesn't terminate on infinite iterators.
+    #[must_use]
+    fn last(&'item self) -> Option<Self::Advance>;
+}
+
+impl<'item> SortedBreadthFirst<'item> for BaseCase {
+    #[inline(always)]
+    fn first(&'item self) -> Option<Self::Advance> {
+        Some(())
+    }
+    #[inline(always)]
+    fn last(&'item self) -> Option<Self::Advance> {
+        Some(())
+    }
+}
+
+impl<'item, Head: Sorted, Tail: SortedBreadthFirst<'item>> SortedBreadthFirst<'item>
+    for BreadthFirstZipped<'item, Head, Tail>
+where
+    Head::Item: 'item,
+    (&'item Head::Item, Tail::Advance): Flatten,
+{
+    #[inline(always)]
+    fn first(&'item self) -> Option<Self::Advance> {
+        Some((self.at(0)?, self.tail.first()?))
+    }
+    #[inline(always)]
+    fn last(&'item self) -> Option<Self::Advance> {
+        self.last_of_each()
+    }
+}
+
+impl<'item, Tail: SortedBreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Smallest combination, both componentwise and lexicographically, without iterating over any others.
+    /// For iterators that aren't `Sorted`, there's no shortcut: drain `next` and compare.
+    #[inline]
+    #[must_use]
+    pub fn min_combination(&'item self) -> Option<<Tail::Advance as Flatten>::Flattened> {
+        self.tail.first().map(Flatten::flatten)
+    }
+    /// Largest combination, both componentwise and lexicographically, without iterating over any others.
+    /// Walks each iterator to its end, so this doesn't terminate on infinite iterators.
+    /// For iterators that aren't `Sorted`, there's no shortcut: drain `next` and compare.
+    #[inline]
+    #[must_use]
+    pub fn max_combination(&'item self) -> Option<<Tail::Advance as Flatten>::Flattened> {
+        self.tail.last().map(Flatten::flatten)
+    }
+    /// Both `min_combination` and `max_combination`.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::type_complexity)]
+    pub fn minmax_combination(
+        &'item self,
+    ) -> Option<(
+        <Tail::Advance as Flatten>::Flattened,
+        <Tail::Advance as Flatten>::Flattened,
+    )> {
+        self.min_combination().zip(self.max_combination())
+    }
+    /// Combinations minimizing and maximizing `key` over every combination, without iterating over any others as long as `key` keeps its `promise`.
+    /// If it visibly doesn't (i.e. the key of the supposed minimum exceeds that of the supposed maximum),
+    /// falls back to scanning every combination with random access (like `lexicographic`), so either way this zip's own position is untouched.
+    /// A broken promise that isn't visible at the extremes gives wrong answers (but is never undefined behavior).
+    ///
+    /// Ties go to the lexicographically first minimum and last maximum, like `Iterator::min_by_key` and `Iterator::max_by_key`.
+    #[inline]
+    #[must_use]
+    #[allow(clippy::type_complexity)]
+    pub fn extremum_by_key<K: Ord, F: FnMut(&<Tail::Advance as Flatten>::Flattened) -> K>(
+        &'item self,
+        promise: Monotonic,
+        mut key: F,
+    ) -> Option<(
+        <Tail::Advance as Flatten>::Flattened,
+        <Tail::Advance as Flatten>::Flattened,
+    )>
+    where
+        <Tail::Advance as Flatten>::Flattened: Clone,
+        Tail::Indices: Default + IntoArray,
+        <Tail::Indices as IntoArray>::Array: AsMut<[usize]> + AsRef<[usize]>,
+    {
+        let (first, last) = self.minmax_combination()?;
+        let (promised_min, promised_max) = match promise {
+            Monotonic::Increasing => (first, last),
+            Monotonic::Decreasing => (last, first),
+        };
+        if key(&promised_min) <= key(&promised_max) {
+            return Some((promised_min, promised_max));
+        }
+        let mut scan = self.lexicographic();
+        let mut min = scan.next()?;
+        let (mut min_key, mut max_key) = (key(&min), key(&min));
+        let mut max = min.clone();
+        for combination in scan {
+            let combination_key = key(&combination);
+            if combination_key < min_key {
+                min_key = combination_key;
+                min = combination;
+                continue;
+            }
+            if combination_key >= max_key {
+                max_key = combination_key;
+                max = combination;
+            }
+        }
+        Some((min, max))
+    }
+}
+
+/// Iterator over every combination in ascending lexicographic order, given `Sorted` iterators.
+pub struct Lexicographic<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+where
+    Tail::Indices: IntoArray,
+{
+    /// Zip handing out references (but never moved: we only use random access).
+    manager: &'item BreadthFirstManager<'item, Tail, Schedule>,
+    /// Index into each iterator of the next combination, or `None` once we've run out.
+    indices: Option<<Tail::Indices as IntoArray>::Array>,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>> Iterator
+    for Lexicographic<'item, Tail, Schedule>
+where
+    Tail::Indices: IntoArray,
+    <Tail::Indices as IntoArray>::Array: AsMut<[usize]> + AsRef<[usize]>,
+{
+    type Item = <Tail::Advance as Flatten>::Flattened;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let indices = self.indices.as_mut()?;
+        let Some(combination) = self.manager.at(indices.as_ref()) else {
+            self.indices = None;
+            return None;
+        };
+        // Odometer: bump the last index, and whenever one runs off the end of its iterator, reset it and carry to the left.
+        let mut carried = true;
+        for axis in (0..indices.as_ref().len()).rev() {
+            let index = indices.as_mut().get_mut(axis)?;
+            *index = index.saturating_add(1);
+            if self.manager.at(indices.as_ref()).is_some() {
+                carried = false;
+                break;
+            }
+            let reset = indices.as_mut().get_mut(axis)?;
+            *reset = 0;
+        }
+        if carried {
+            self.indices = None;
+        }
+        Some(combination)
+    }
+}
+
+impl<'item, Tail: SortedBreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+where
+    Tail::Indices: Default + IntoArray,
+{
+    /// Every combination in ascending lexicographic order (last iterator fastest), instead of by index sum, e.g. for a merge join.
+    /// Since each iterator is `Sorted`, that's just counting up like an odometer: no heap or comparisons needed.
+    /// Uses random access (like `at`) only, so this zip's own position is untouched.
+    #[inline]
+    #[must_use]
+    pub fn lexicographic(&'item self) -> Lexicographic<'item, Tail, Schedule> {
+        Lexicographic {
+            manager: self,
+            indices: Some(Tail::Indices::default().into_array()),
+        }
+    }
+}
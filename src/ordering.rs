@@ -0,0 +1,147 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Visit combinations in order of any priority of their indices, not just the index sum.
+
+use crate::{homogeneous::IntoArray, BreadthFirst, BreadthFirstManager, Flatten};
+use ::core::marker::PhantomData;
+
+/// Order in which to visit combinations: nondecreasing priority of their indices (one per iterator, from left to right).
+///
+/// A priority must never decrease as any one index increases, and must be at least as large as every index,
+/// so that every combination with priority `p` has every index at most `p`.
+/// Breaking either promise gives wrong answers (but is never undefined behavior).
+pub trait Priority {
+    /// Priority of the combination at these indices: lower comes first.
+    #[must_use]
+    fn priority(indices: &[usize]) -> usize;
+}
+
+/// Sum of indices, i.e. the order `next` already uses (which is much faster, since it never looks at anything out of order).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct IndexSum;
+
+impl Priority for IndexSum {
+    #[inline(always)]
+    fn priority(indices: &[usize]) -> usize {
+        indices
+            .iter()
+            .fold(0_usize, |sum, &index| sum.saturating_add(index))
+    }
+}
+
+/// Largest index, i.e. growing a cube of combinations one layer at a time.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct MaxIndex;
+
+impl Priority for MaxIndex {
+    #[inline(always)]
+    fn priority(indices: &[usize]) -> usize {
+        indices.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Iterator over every combination in nondecreasing `P`-priority, ties in lexicographic order of their indices.
+pub struct ByPriority<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>, P>
+where
+    Tail::Indices: IntoArray,
+{
+    /// Zip handing out references (but never moved: we only use random access).
+    manager: &'item BreadthFirstManager<'item, Tail, Schedule>,
+    /// Indices of the next candidate in the current sweep, or `None` once we've run out.
+    indices: Option<<Tail::Indices as IntoArray>::Array>,
+    /// Priority currently being visited, which is also the largest index in the current sweep.
+    level: usize,
+    /// Whether any combination in the current sweep had an index equal to `level`, i.e. whether the iterators might be longer.
+    on_edge: bool,
+    /// Highest priority of any combination in the current sweep.
+    highest: usize,
+    /// Which priority to use.
+    priority: PhantomData<P>,
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>, P>
+    ByPriority<'item, Tail, Schedule, P>
+where
+    Tail::Indices: IntoArray,
+    <Tail::Indices as IntoArray>::Array: AsMut<[usize]> + AsRef<[usize]>,
+{
+    /// Move to the next candidate in the current sweep (every index from 0 to `level`, like an odometer),
+    /// or start the next sweep once this one is over, or return `None` once no later sweep could find anything.
+    #[inline]
+    fn step(&mut self) -> Option<()> {
+        let level = self.level;
+        let indices = self.indices.as_mut()?;
+        for index in indices.as_mut().iter_mut().rev() {
+            if *index < level {
+                *index = index.saturating_add(1);
+                return Some(());
+            }
+            *index = 0;
+        }
+        // Every combination left has every index below `level`, and every one of them has priority at most `highest`
+        if !self.on_edge && self.highest <= level {
+            self.indices = None;
+            return None;
+        }
+        self.level = level.checked_add(1)?;
+        self.on_edge = false;
+        self.highest = 0;
+        Some(())
+    }
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>, P: Priority> Iterator
+    for ByPriority<'item, Tail, Schedule, P>
+where
+    Tail::Indices: IntoArray,
+    <Tail::Indices as IntoArray>::Array: AsMut<[usize]> + AsRef<[usize]>,
+{
+    type Item = <Tail::Advance as Flatten>::Flattened;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let indices = self.indices.as_ref()?.as_ref();
+            let found = self.manager.at(indices).map(|combination| {
+                let priority = P::priority(indices);
+                self.on_edge |= indices.contains(&self.level);
+                self.highest = self.highest.max(priority);
+                (priority == self.level).then_some(combination)
+            });
+            let _: Option<()> = self.step();
+            if let Some(Some(combination)) = found {
+                return Some(combination);
+            }
+        }
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+where
+    Tail::Indices: Default + IntoArray,
+{
+    /// Every combination in nondecreasing priority `P` of its indices (see `Priority`), e.g. `by_priority::<MaxIndex>()`,
+    /// with ties in lexicographic order of their indices, just as `next` breaks ties within an index sum.
+    /// Each priority sweeps every combination whose indices are all at most that priority,
+    /// so this is much slower than `next`: use it only for an order `next` can't give.
+    /// Uses random access (like `at`) only, so this zip's own position is untouched.
+    #[inline]
+    #[must_use]
+    pub fn by_priority<P: Priority>(&'item self) -> ByPriority<'item, Tail, Schedule, P> {
+        ByPriority {
+            manager: self,
+            indices: Some(Tail::Indices::default().into_array()),
+            level: 0,
+            on_edge: false,
+            highest: 0,
+            priority: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,108 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Standard `Iterator` over cloned (owned) combinations, to use with the rest of the `Iterator` ecosystem.
+
+use crate::{sealed, BreadthFirst, BreadthFirstManager, Flatten};
+use ::core::marker::PhantomData;
+
+/// Clone every element of a nested tuple of references like `(&A, (&B, (&C, ())))` into `(A, (B, (C, ())))`.
+///
+/// Every `Copy` type is `Clone` (Rust requires it), so this covers `Copy` types too, where each clone is just a copy:
+/// a separate `Copy` bound wouldn't admit any type that `Clone` doesn't already.
+pub trait CloneAll: sealed::Nested {
+    /// Nested tuple of owned values.
+    type Cloned: Flatten;
+    /// Clone every element.
+    #[must_use]
+    fn clone_all(self) -> Self::Cloned;
+}
+
+impl CloneAll for () {
+    type Cloned = ();
+    #[inline(always)]
+    fn clone_all(self) -> Self::Cloned {}
+}
+
+impl<Head: Clone, Tail: CloneAll> CloneAll for (&Head, Tail)
+where
+    (Head, Tail::Cloned): Flatten,
+{
+    type Cloned = (Head, Tail::Cloned);
+    #[inline(always)]
+    fn clone_all(self) -> Self::Cloned {
+        (self.0.clone(), self.1.clone_all())
+    }
+}
+
+/// Standard `Iterator` over cloned combinations from a breadth-first zip.
+pub struct Owned<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>> {
+    /// Zip handing out references.
+    manager: &'item BreadthFirstManager<'item, Tail, Schedule>,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>> Iterator
+    for Owned<'item, Tail, Schedule>
+where
+    Tail::Advance: CloneAll,
+{
+    type Item = <<Tail::Advance as CloneAll>::Cloned as Flatten>::Flattened;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.manager
+            .next_nested()
+            .map(|nested| nested.clone_all().flatten())
+    }
+}
+
+/// Standard `Iterator` over cloned combinations from a breadth-first zip, each converted into `T` with `From`.
+pub struct Converted<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>, T> {
+    /// Zip handing out references.
+    manager: &'item BreadthFirstManager<'item, Tail, Schedule>,
+    /// Type each combination becomes.
+    target: PhantomData<fn() -> T>,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>, T> Iterator
+    for Converted<'item, Tail, Schedule, T>
+where
+    Tail::Advance: CloneAll,
+    T: From<<<Tail::Advance as CloneAll>::Cloned as Flatten>::Flattened>,
+{
+    type Item = T;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.manager
+            .next_nested()
+            .map(|nested| nested.clone_all().flatten_into())
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Standard `Iterator` cloning each combination instead of borrowing it.
+    /// Advances this zip as it goes, so mixing calls to `next` here and there splits the output between them.
+    /// Also the way to use one zip as an iterator in another: e.g. `(inner.owned(), 0..3).breadth_first()` composes just fine,
+    /// since the outer zip caches each combination instead of rewinding the inner zip.
+    #[inline]
+    #[must_use]
+    pub const fn owned(&'item self) -> Owned<'item, Tail, Schedule> {
+        Owned { manager: self }
+    }
+    /// Like `owned`, but converting each combination into `T`, e.g. a struct with a named field per iterator
+    /// instead of a tuple, given an implementation of `From<(A, B, C)>` for it.
+    #[inline]
+    #[must_use]
+    pub const fn breadth_first_zip_as<T>(&'item self) -> Converted<'item, Tail, Schedule, T> {
+        Converted {
+            manager: self,
+            target: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Split a breadth-first zip across threads with `rayon`.
+
+use crate::{owned::CloneAll, streaming::Lend, BreadthFirstZip, Flatten};
+use alloc::vec::Vec;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+/// Every combination of an element of `head` with a combination of `rest`, split across threads by element of `head`.
+///
+/// Each worker gets one element of `head` and the full product of `rest`, which balances well when `head` is long.
+/// The output is the same set of (cloned) combinations as zipping everything together, but in no particular order.
+#[inline]
+pub fn par_by_axis0<Head, Rest, Owned>(
+    head: Head,
+    rest: Rest,
+) -> impl ParallelIterator<Item = <(Head::Item, Owned) as Flatten>::Flattened>
+where
+    Head: Iterator,
+    Head::Item: Clone + Send,
+    Rest: BreadthFirstZip<'static> + Clone + Send + Sync,
+    Rest::Nested: Lend,
+    for<'borrow> <Rest::Nested as Lend>::Lent<'borrow>: CloneAll<Cloned = Owned>,
+    (Head::Item, Owned): Flatten,
+    <(Head::Item, Owned) as Flatten>::Flattened: Send,
+{
+    head.collect::<Vec<_>>()
+        .into_par_iter()
+        .flat_map_iter(move |first| {
+            let manager = rest.clone().breadth_first();
+            ::core::iter::from_fn(|| manager.next_with(Lend::lend).map(CloneAll::clone_all))
+                .map(|others| (first.clone(), others).flatten())
+                .collect::<Vec<_>>()
+        })
+}
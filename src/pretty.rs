@@ -0,0 +1,74 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Print a small breadth-first zip as a table, e.g. to eyeball its order while debugging.
+
+use crate::{homogeneous::IntoArray, BreadthFirst, BreadthFirstManager};
+use ::core::{fmt::Display, iter::once};
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+where
+    Tail::Advance: IntoArray,
+{
+    /// Drain every remaining combination into a right-aligned table with one row per combination and one column per iterator,
+    /// after a header row and a column of index sums (written only on the first row of each index sum, to show where each begins).
+    /// Meant for a handful of short iterators (say, up to three): every cell is formatted before anything is aligned.
+    #[inline]
+    #[must_use]
+    pub fn debug_table<T: Display + 'item>(&'item self) -> String
+    where
+        <Tail::Advance as IntoArray>::Array: AsRef<[&'item T]>,
+    {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        rows.push(
+            once("index sum".to_owned())
+                .chain((0..Tail::DEPTH).map(|axis| format!("#{axis}")))
+                .collect(),
+        );
+        let mut previous = None;
+        while let Some(array) = self.next_array() {
+            let index_sum = self.index_sum.get();
+            let label = if index_sum == previous {
+                String::new()
+            } else {
+                index_sum.map_or_else(String::new, |sum| sum.to_string())
+            };
+            previous = index_sum;
+            rows.push(
+                once(label)
+                    .chain(array.as_ref().iter().map(ToString::to_string))
+                    .collect(),
+            );
+        }
+        let widths: Vec<usize> = (0..=Tail::DEPTH)
+            .map(|column| {
+                rows.iter()
+                    .filter_map(|row| row.get(column))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let mut table = String::new();
+        for row in rows {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{cell:>width$}"))
+                .collect();
+            table.push_str(&cells.join(" | "));
+            table.push('\n');
+        }
+        table
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Work with one iterator's elements across every combination, without flattening the rest.
+
+use crate::{sealed, BreadthFirst, BreadthFirstManager, Flatten};
+use ::core::iter::Sum;
+
+/// Pick the `I`th element out of a nested tuple like `(A, (B, (C, ())))`.
+pub trait Component<const I: usize>: sealed::Nested {
+    /// Type of the `I`th element.
+    type Output;
+    /// Move the `I`th element out.
+    #[must_use]
+    fn component(self) -> Self::Output;
+}
+
+breadth_first_zip_macros::implement_component!(); // Implement for (A, Tail), (A, (B, Tail)), (A, (B, (C, Tail))), ...
+
+/// Split a nested tuple like `(A, (B, (C, ())))` after its first `K` elements, e.g. into `(A, (B, ()))` and `(C, ())`.
+pub trait SplitPrefix<const K: usize>: sealed::Nested {
+    /// First `K` elements, as a nested tuple ending in `()`.
+    type Prefix;
+    /// Everything after the first `K` elements.
+    type Suffix;
+    /// Move the first `K` elements and the rest out separately.
+    #[must_use]
+    fn split_prefix(self) -> (Self::Prefix, Self::Suffix);
+}
+
+breadth_first_zip_macros::implement_split_prefix!(); // Implement for (A, Tail), (A, (B, Tail)), (A, (B, (C, Tail))), ...
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Iterator skipping every combination whose `I`th element doesn't satisfy `predicate`, e.g. `filter_by_component::<1, _>(|&&x| x.is_multiple_of(2))`.
+    /// Indices aren't renumbered: the output is exactly the unfiltered output minus whatever was dropped, in the same order,
+    /// so dropped elements still count toward each index sum (and are still visited, though never flattened).
+    /// This only filters the output: to skip exploring the rest of the list for an element at all, `prune` its iterator instead.
+    #[inline]
+    pub fn filter_by_component<
+        const I: usize,
+        Predicate: FnMut(&<Tail::Advance as Component<I>>::Output) -> bool + 'item,
+    >(
+        &'item self,
+        mut predicate: Predicate,
+    ) -> impl Iterator<Item = <Tail::Advance as Flatten>::Flattened> + 'item
+    where
+        Tail::Advance: Component<I> + Copy,
+    {
+        ::core::iter::from_fn(|| self.next_nested())
+            .filter(move |&nested| predicate(&nested.component()))
+            .map(Flatten::flatten)
+    }
+    /// Sum the `I`th element of each remaining combination, e.g. `sum_component::<1, _>()` for the middle of a triple.
+    #[inline]
+    #[must_use]
+    pub fn sum_component<const I: usize, S: Sum<<Tail::Advance as Component<I>>::Output>>(
+        &'item self,
+    ) -> S
+    where
+        Tail::Advance: Component<I>,
+    {
+        ::core::iter::from_fn(|| self.next_nested())
+            .map(Component::component)
+            .sum()
+    }
+}
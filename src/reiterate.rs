@@ -0,0 +1,142 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! In-crate stand-in for the `reiterator` crate, used when the `external-reiterator` feature is off.
+//! Same API (as far as this crate uses it), one fewer dependency, and no `unsafe`.
+
+use ::alloc::boxed::Box;
+use ::core::cell::{Cell, OnceCell, RefCell};
+
+/// One chunk per bit of `usize`, which is enough to cache every index a `usize` can name.
+#[allow(clippy::as_conversions)] // `u32` to `usize` never truncates on any platform Rust supports
+const CHUNKS: usize = usize::BITS as usize;
+
+/// Cached elements starting at some index, allocated all at once the first time we reach that index.
+type Chunk<Item> = OnceCell<Box<[OnceCell<Item>]>>;
+
+/// Lightweight pair of a cached element and its position in the original iterator, as returned by `Reiterator::get`.
+///
+/// ```
+/// use breadth_first_zip::{indexed, Indexed};
+/// let element: Indexed<'_, char> = indexed(2, &'c');
+/// assert_eq!(element.index, 2);
+/// assert_eq!(element.value, &'c');
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Indexed<'item, A> {
+    /// Position of this element in the original iterator.
+    pub index: usize,
+    /// Cached element.
+    pub value: &'item A,
+}
+
+/// Pair a cached element with its position in the original iterator.
+#[inline(always)]
+#[must_use]
+pub const fn indexed<A>(index: usize, value: &A) -> Indexed<'_, A> {
+    Indexed { index, value }
+}
+
+/// Caching, rewindable iterator adapter: pulls each element once, then hands out references to it from then on.
+#[allow(clippy::partial_pub_fields)] // `index` is public in the `reiterator` crate too
+pub struct Reiterator<Iter: Iterator> {
+    /// Original iterator, pulled only on a cache miss.
+    iter: RefCell<Iter>,
+    /// Cached elements: chunk `k` holds the `2^k` elements starting at index `2^k - 1`, so none of them ever has to move.
+    chunks: [Chunk<Iter::Item>; CHUNKS],
+    /// How many elements we've cached so far.
+    cached: Cell<usize>,
+    /// Whether `iter` has returned `None`, after which we never pull it again.
+    exhausted: Cell<bool>,
+    /// Index of the current element.
+    pub index: Cell<usize>,
+}
+
+/// Which chunk holds the element at `index`, and where in that chunk.
+#[inline(always)]
+fn locate(index: usize) -> Option<(u32, usize)> {
+    let one_based = index.checked_add(1)?;
+    let chunk = one_based.ilog2();
+    Some((chunk, one_based.checked_sub(1_usize.checked_shl(chunk)?)?))
+}
+
+impl<Iter: Iterator> Reiterator<Iter> {
+    /// Wrap an iterator without pulling anything from it yet.
+    #[inline]
+    #[must_use]
+    pub fn new(iter: Iter) -> Self {
+        Self {
+            iter: RefCell::new(iter),
+            chunks: ::core::array::from_fn(|_| OnceCell::new()),
+            cached: Cell::new(0),
+            exhausted: Cell::new(false),
+            index: Cell::new(0),
+        }
+    }
+    /// Move back to the first element without pulling anything.
+    #[inline]
+    pub fn restart(&self) {
+        self.index.set(0);
+    }
+    /// Element at an arbitrary index, pulling (and caching) everything up to it if we haven't already.
+    #[inline]
+    #[must_use]
+    pub fn at(&self, index: usize) -> Option<&Iter::Item> {
+        while self.cached.get() <= index {
+            if self.exhausted.get() {
+                return None;
+            }
+            let Some(item) = self.iter.borrow_mut().next() else {
+                self.exhausted.set(true);
+                return None;
+            };
+            let (chunk, offset) = locate(self.cached.get())?;
+            let slots = self
+                .chunks
+                .get(usize::try_from(chunk).ok()?)?
+                .get_or_init(|| {
+                    ::core::iter::repeat_with(OnceCell::new)
+                        .take(1_usize << chunk)
+                        .collect()
+                });
+            slots.get(offset)?.get_or_init(|| item);
+            self.cached.set(self.cached.get().checked_add(1)?);
+        }
+        let (chunk, offset) = locate(index)?;
+        self.chunks
+            .get(usize::try_from(chunk).ok()?)?
+            .get()?
+            .get(offset)?
+            .get()
+    }
+    /// Current element, or `None` if the original iterator ran out before reaching it.
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> Option<Indexed<'_, Iter::Item>> {
+        let index = self.index.get();
+        self.at(index).map(|value| indexed(index, value))
+    }
+    /// Move to the next element and return it, or `None` if the original iterator runs out first.
+    #[inline]
+    pub fn next(&self) -> Option<Indexed<'_, Iter::Item>> {
+        self.index.set(self.index.get().checked_add(1)?);
+        self.get()
+    }
+}
+
+/// Wrap any iterator in a `Reiterator`.
+pub trait Reiterate: Iterator + Sized {
+    /// Wrap this iterator in a `Reiterator` without pulling anything from it yet.
+    #[inline]
+    #[must_use]
+    fn reiterate(self) -> Reiterator<Self> {
+        Reiterator::new(self)
+    }
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl<Iter: Iterator> Reiterate for Iter {}
@@ -0,0 +1,27 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Deterministic subsets of a breadth-first zip, e.g. to sample a huge search space.
+
+use crate::{BreadthFirst, BreadthFirstManager, Flatten};
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Iterator over every `k`th combination, starting with the first, like `owned().step_by(k)`.
+    /// Skipped combinations are never flattened, let alone cloned.
+    /// # Panics
+    /// If `k` is zero.
+    #[inline]
+    pub fn every_nth(
+        &'item self,
+        k: usize,
+    ) -> impl Iterator<Item = <Tail::Advance as Flatten>::Flattened> + 'item {
+        ::core::iter::from_fn(|| self.next_nested())
+            .step_by(k)
+            .map(Flatten::flatten)
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Lending-iterator interface: each output borrows the zip only until the next call,
+//! so nothing ties the zip to the lifetime of its first borrow.
+
+#[cfg(feature = "alloc")]
+use crate::BreadthFirstZip;
+use crate::{sealed, BaseCase, BreadthFirst, BreadthFirstManager, BreadthFirstZipped, Flatten};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Like `Iterator`, but each item may borrow from the iterator itself until the next call to `next`.
+pub trait LendingBreadthFirst {
+    /// Output, borrowing from `self` for `'borrow`.
+    type Item<'borrow>
+    where
+        Self: 'borrow;
+    /// Fallibly choose the next output.
+    #[must_use]
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
+/// Like `BreadthFirst` but generic over the lifetime of each borrow rather than fixed ahead of time.
+pub trait Lend: sealed::BreadthFirst {
+    /// Nested tuple of references valid for `'borrow`, e.g. `(&'borrow A, (&'borrow B, ()))`.
+    type Lent<'borrow>
+    where
+        Self: 'borrow;
+    /// Fallibly choose the next output.
+    #[must_use]
+    fn lend(&self, index_sum: usize) -> Option<Self::Lent<'_>>;
+}
+
+impl Lend for BaseCase {
+    type Lent<'borrow> = ();
+    #[inline(always)]
+    fn lend(&self, index_sum: usize) -> Option<Self::Lent<'_>> {
+        BreadthFirst::next(self, index_sum)
+    }
+}
+
+impl<'item, Head: Iterator, Tail: BreadthFirst<'item> + Lend> Lend
+    for BreadthFirstZipped<'item, Head, Tail>
+{
+    type Lent<'borrow>
+        = (&'borrow Head::Item, Tail::Lent<'borrow>)
+    where
+        Self: 'borrow;
+    #[inline(always)]
+    fn lend(&self, index_sum: usize) -> Option<Self::Lent<'_>> {
+        self.next_with(index_sum, Tail::lend)
+    }
+}
+
+impl<'item, Tail: BreadthFirst<'item> + Lend, Schedule: Iterator<Item = usize>> LendingBreadthFirst
+    for BreadthFirstManager<'item, Tail, Schedule>
+where
+    for<'borrow> Tail::Lent<'borrow>: Flatten,
+{
+    type Item<'borrow>
+        = <Tail::Lent<'borrow> as Flatten>::Flattened
+    where
+        Self: 'borrow;
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        self.next_with(Tail::lend).map(Flatten::flatten)
+    }
+}
+
+/// Move a tuple of `Vec`s into a zip that owns them, so it can be returned from functions and moved around freely.
+///
+/// Borrow combinations from it through `LendingBreadthFirst::next`, which only holds each borrow until the next call.
+#[cfg(feature = "alloc")]
+pub trait IntoOwning {
+    /// Zip owning every `Vec`.
+    type Owning: LendingBreadthFirst;
+    /// Move each `Vec` into a zip that owns it.
+    #[must_use]
+    fn into_owning(self) -> Self::Owning;
+}
+
+#[cfg(feature = "alloc")]
+breadth_first_zip_macros::implement_owning!(); // Implement for (Vec<A>,), (Vec<A>, Vec<B>), ...
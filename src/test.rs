@@ -6,7 +6,97 @@
 
 //! Super simple test suite.
 
-use crate::BreadthFirstZip;
+use crate::{
+    BaseCase, BreadthFirst, BreadthFirstManager, BreadthFirstZip, BreadthFirstZipped, EmptyAxis,
+    Flatten, State,
+};
+use ::core::ops::Range;
+
+breadth_first_zip_macros::implement_roundtrip_tests!(); // Test (A,), (A, B), (A, B, C), ...
+
+/// Iterators shared across tests.
+mod fixture {
+    use ::core::ops::Range;
+
+    /// Three iterators over `0..3`, i.e. 27 combinations: the zip most tests here start from.
+    pub fn triples() -> (Range<u8>, Range<u8>, Range<u8>) {
+        (0..3, 0..3, 0..3)
+    }
+}
+
+#[test]
+fn flatten_arities() {
+    let () = ().flatten();
+    assert_eq!(('a', ()).flatten(), ('a',));
+    assert_eq!(('a', ('b', ())).flatten(), ('a', 'b'));
+    assert_eq!(('a', ('b', ('c', ()))).flatten(), ('a', 'b', 'c'));
+    assert_eq!(
+        ('a', ('b', ('c', ('d', ())))).flatten(),
+        ('a', 'b', 'c', 'd')
+    );
+    assert_eq!(
+        ('a', ('b', ('c', ('d', ('e', ()))))).flatten(),
+        ('a', 'b', 'c', 'd', 'e')
+    );
+    assert_eq!(
+        ('a', ('b', ('c', ('d', ('e', ('f', ())))))).flatten(),
+        ('a', 'b', 'c', 'd', 'e', 'f')
+    );
+    assert_eq!(
+        ('a', ('b', ('c', ('d', ('e', ('f', ('g', ()))))))).flatten(),
+        ('a', 'b', 'c', 'd', 'e', 'f', 'g')
+    );
+    assert_eq!(
+        ('a', ('b', ('c', ('d', ('e', ('f', ('g', ('h', ())))))))).flatten(),
+        ('a', 'b', 'c', 'd', 'e', 'f', 'g', 'h')
+    );
+}
+
+#[test]
+fn index_arithmetic_edges() {
+    use crate::{element_cost, next_fits, remaining_budget};
+    assert_eq!(element_cost(2, 3), Some(6));
+    assert_eq!(element_cost(usize::MAX, 2), None);
+    assert_eq!(remaining_budget(3, 3), Some(0));
+    assert_eq!(remaining_budget(3, 4), None);
+    assert!(next_fits(2, 1, 3));
+    assert!(!next_fits(3, 1, 3));
+    assert!(!next_fits(usize::MAX, 1, usize::MAX));
+    // Stepping along the first iterator costs more than any index sum we'll reach, so it never moves.
+    let iter =
+        BreadthFirstManager::with_weights(fixture::triples().unflatten(), [usize::MAX, 1, 1]);
+    let first_nine: Vec<_> = ::core::iter::from_fn(|| iter.next()).take(9).collect();
+    assert_eq!(first_nine.len(), 9);
+    assert!(first_nine.iter().all(|&(&first, _, _)| first == 0));
+}
+
+#[test]
+fn uneven_lengths_match_brute_force() {
+    let [a, b, c, d] = [1_u8, 5, 2, 4].map(|length| 0..length);
+    let iter = (a.clone(), b.clone(), c.clone(), d.clone()).breadth_first();
+    let mut expected = vec![];
+    for w in a {
+        for x in b.clone() {
+            for y in c.clone() {
+                for z in d.clone() {
+                    expected.push((w, x, y, z));
+                }
+            }
+        }
+    }
+    expected.sort_by_key(|&(w, x, y, z)| w + x + y + z); // stable, so lexicographic within each index sum
+    assert_eq!(iter.owned().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn nullary() {
+    // The empty product has exactly one element: the empty combination.
+    let iter = ().try_breadth_first().expect("no iterators, so none of them are empty");
+    assert_eq!(iter.next(), Some(()));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+    assert!(!iter.schedule_over());
+}
 
 #[test]
 fn triples() {
@@ -50,6 +140,1654 @@ fn triples() {
     assert_eq!(iter.next(), None);
 }
 
+#[test]
+fn schedule() {
+    let iter =
+        BreadthFirstManager::with_schedule(fixture::triples().unflatten(), [2, 0].into_iter());
+    // index sum = 2
+    assert_eq!(iter.next(), Some((&0, &0, &2)));
+    assert_eq!(iter.next(), Some((&0, &1, &1)));
+    assert_eq!(iter.next(), Some((&0, &2, &0)));
+    assert_eq!(iter.next(), Some((&1, &0, &1)));
+    assert_eq!(iter.next(), Some((&1, &1, &0)));
+    assert_eq!(iter.next(), Some((&2, &0, &0)));
+    // index sum = 0
+    assert_eq!(iter.next(), Some((&0, &0, &0)));
+    // schedule over
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn weights() {
+    let indices = 0..3_u8;
+    let iter = BreadthFirstManager::with_weights(fixture::triples().unflatten(), [2, 1, 1]);
+    // weighted index sum = 0
+    assert_eq!(iter.next(), Some((&0, &0, &0)));
+    // weighted index sum = 1
+    assert_eq!(iter.next(), Some((&0, &0, &1)));
+    assert_eq!(iter.next(), Some((&0, &1, &0)));
+    // weighted index sum = 2
+    assert_eq!(iter.next(), Some((&0, &0, &2)));
+    assert_eq!(iter.next(), Some((&0, &1, &1)));
+    assert_eq!(iter.next(), Some((&0, &2, &0)));
+    assert_eq!(iter.next(), Some((&1, &0, &0)));
+    // and so on:
+    let mut expected = vec![];
+    for a in indices.clone() {
+        for b in indices.clone() {
+            for c in indices.clone() {
+                expected.push((a, b, c));
+            }
+        }
+    }
+    expected.sort_by_key(|&(a, b, c)| 2 * a + b + c);
+    let weighted = BreadthFirstManager::with_weights(fixture::triples().unflatten(), [2, 1, 1]);
+    let actual: Vec<_> = weighted.owned().collect();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn weights_without_one() {
+    let iter = BreadthFirstManager::with_weights(fixture::triples().unflatten(), [2, 2, 2]);
+    assert_eq!(iter.next(), Some((&0, &0, &0)));
+    // weighted index sum 1 has no combinations, so we move straight on to 2
+    assert_eq!(iter.next(), Some((&0, &0, &1)));
+    iter.reset();
+    let actual: Vec<_> = iter.owned().collect();
+    assert_eq!(actual.len(), 27);
+    assert!(actual.iter().map(|&(a, b, c)| a + b + c).is_sorted());
+    assert_eq!(iter.next(), None);
+    let mut infinite = BreadthFirstManager::with_weights((0_u8.., 0..3_u8).unflatten(), [2, 2]);
+    infinite.tail_mut().tail_mut().prune(&|_| false);
+    assert_eq!(infinite.next(), None);
+}
+
+#[test]
+fn arrays() {
+    let tuples = fixture::triples().breadth_first();
+    let arrays = fixture::triples().breadth_first();
+    while let Some(tuple) = tuples.next() {
+        assert_eq!(arrays.next_array(), Some(<[_; 3]>::from(tuple)));
+    }
+    assert_eq!(arrays.next_array(), None);
+}
+
+#[test]
+fn array_zip() {
+    use crate::uniform::BreadthFirstArrayZip;
+    let axis = || 0..3_u8;
+    let one = ([axis()].breadth_first(), (axis(),).breadth_first());
+    assert!(::core::iter::from_fn(|| one.0.next()).eq(::core::iter::from_fn(|| one.1.next_array())));
+    let three = (
+        [axis(), axis(), axis()].breadth_first(),
+        (axis(), axis(), axis()).breadth_first(),
+    );
+    assert!(
+        ::core::iter::from_fn(|| three.0.next()).eq(::core::iter::from_fn(|| three.1.next_array()))
+    );
+    let five = (
+        [axis(), axis(), axis(), axis(), axis()].breadth_first(),
+        (axis(), axis(), axis(), axis(), axis()).breadth_first(),
+    );
+    let mut count = 0_usize;
+    assert!(::core::iter::from_fn(|| five.0.next())
+        .inspect(|_| count += 1)
+        .eq(::core::iter::from_fn(|| five.1.next_array())));
+    assert_eq!(count, 243);
+}
+
+#[test]
+fn uniform_matches_tuple() {
+    use crate::uniform::BreadthFirstArrayZip;
+    let array = [0..3_u8, 0..1_u8, 0..4_u8].breadth_first();
+    let tuple = (0..3_u8, 0..1_u8, 0..4_u8).breadth_first();
+    let mut count = 0_usize;
+    while let Some(combination) = array.next() {
+        assert_eq!(Some(combination), tuple.next_array());
+        count += 1;
+    }
+    assert_eq!(tuple.next_array(), None);
+    assert_eq!(count, 12);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn boxed_axes() {
+    use crate::uniform::{BreadthFirstArrayZip, BreadthFirstVecZipped};
+    let axes: Vec<Box<dyn Iterator<Item = u8>>> = vec![
+        Box::new(0..3),
+        Box::new((0..3).rev().rev()),
+        Box::new(0..=2),
+        Box::new([0, 1, 2].into_iter()),
+    ];
+    let boxed = BreadthFirstVecZipped::try_from_iter(axes).unwrap();
+    let range = || 0..3_u8;
+    let array = [range(), range(), range(), range()].breadth_first();
+    let mut count = 0_usize;
+    assert!(::core::iter::from_fn(|| boxed.next())
+        .inspect(|_| count += 1)
+        .eq(::core::iter::from_fn(|| array.next()).map(Vec::from)));
+    assert_eq!(count, 81);
+    let empty: [Box<dyn Iterator<Item = u8>>; 2] = [Box::new(0..3), Box::new(0..0)];
+    assert_eq!(
+        BreadthFirstVecZipped::try_from_iter(empty).err(),
+        Some(EmptyAxis { index: 1 })
+    );
+}
+
+#[test]
+fn indexed_slices() {
+    let (a, b, c) = ([1_u8, 2], [3_u8, 4, 5], [6_u8]);
+    let indexed = crate::uniform::breadth_first_slices([&a[..], &b[..], &c[..]]);
+    let iterated = (a.iter(), b.iter(), c.iter()).breadth_first();
+    assert!(::core::iter::from_fn(|| indexed.next())
+        .eq(::core::iter::from_fn(|| iterated.next_array()).map(|array| array.map(|&x| x))));
+}
+
+#[test]
+fn reiterator() {
+    use crate::Reiterate;
+    let iter = (0..5_u8).map(|i| i * 10).reiterate();
+    assert_eq!(iter.get().map(|indexed| *indexed.value), Some(0));
+    assert_eq!(iter.next().map(|indexed| *indexed.value), Some(10));
+    assert_eq!(iter.next().map(|indexed| *indexed.value), Some(20));
+    // Random access into the cache:
+    iter.index.set(1);
+    assert_eq!(iter.get().map(|indexed| *indexed.value), Some(10));
+    // Random access past the cache:
+    iter.index.set(4);
+    assert_eq!(iter.get().map(|indexed| *indexed.value), Some(40));
+    iter.index.set(5);
+    assert!(iter.get().is_none());
+    iter.restart();
+    assert_eq!(iter.get().map(|indexed| *indexed.value), Some(0));
+}
+
+#[test]
+fn fallible() {
+    let first: [Result<u8, &str>; 2] = [Ok(0), Ok(1)];
+    let second = [Ok(0_u8), Err("bad parse"), Ok(2)];
+    let iter = (first.into_iter(), second.into_iter()).breadth_first();
+    let mut try_iter = iter.try_iter();
+    assert_eq!(try_iter.next(), Some(Ok((&0, &0))));
+    assert_eq!(try_iter.next(), Some(Err(&"bad parse")));
+    assert_eq!(try_iter.next(), None);
+    assert_eq!(try_iter.next(), None);
+}
+
+#[test]
+fn default_is_empty() {
+    let manager: BreadthFirstManager<'_, BreadthFirstZipped<'_, Range<u8>, BaseCase>> =
+        BreadthFirstManager::default();
+    assert_eq!(manager.next(), None);
+}
+
+#[test]
+fn inspect_indices() {
+    let mut sums = vec![];
+    let iter = (0_u8..3, 0_u8..2, 0_u8..4).breadth_first();
+    let combinations = iter
+        .inspect_indices(|indices| sums.push(indices.iter().sum::<usize>()))
+        .count();
+    assert_eq!(combinations, 24);
+    assert_eq!(sums.len(), 24);
+    assert!(sums.windows(2).all(|pair| pair.first() <= pair.last()));
+}
+
+#[test]
+fn len_per_axis() {
+    let pulled = ::core::cell::Cell::new(0_usize);
+    let counted = (0_u8..4).inspect(|_| pulled.set(pulled.get() + 1));
+    let iter = (0_u8..2, 0_u8..3, counted).breadth_first();
+    assert_eq!(iter.len_per_axis(), [2, 3, 4]);
+    assert_eq!(pulled.get(), 0);
+    assert_eq!(iter.next(), Some((&0, &0, &0)));
+}
+
+#[test]
+fn find_combination() {
+    let iter = (0_u8..3, 0_u8..3, 0_u8..3).breadth_first();
+    assert_eq!(
+        iter.find_combination(|&(a, b, c)| a + b + c == 4),
+        Some((&0, &2, &2)),
+    );
+    assert_eq!(iter.next(), Some((&1, &1, &2)));
+}
+
+#[test]
+fn every_nth() {
+    let sampled = fixture::triples().breadth_first();
+    let stepped = fixture::triples().breadth_first();
+    assert!(sampled
+        .every_nth(10)
+        .map(|(&a, &b, &c)| (a, b, c))
+        .eq(stepped.owned().step_by(10)));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn unique_values() {
+    let axis = [0_u8, 0, 1];
+    let iter = (axis.iter(), axis.iter()).breadth_first();
+    let unique: Vec<_> = iter.unique_values().collect();
+    assert_eq!(unique, [(&&0, &&0), (&&0, &&1), (&&1, &&0), (&&1, &&1)],);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn group_by_axis0() {
+    let iter = fixture::triples().breadth_first();
+    let groups = iter.group_by_axis0();
+    assert_eq!(groups.len(), 3);
+    let last = groups
+        .get(&2)
+        .expect("no combinations starting with index 2");
+    assert_eq!(last.len(), 9);
+    assert_eq!(last.first(), Some(&(&2, &0, &0)));
+    assert!(last.iter().all(|&(&a, _, _)| a == 2));
+}
+
+#[test]
+fn progress() {
+    let iter = fixture::triples().breadth_first();
+    let fractions: Vec<f64> = iter.progress().map(|(fraction, _)| fraction).collect();
+    assert_eq!(fractions.len(), 27);
+    assert!(fractions.windows(2).all(|pair| pair.first() < pair.last()));
+    assert!(fractions
+        .last()
+        .is_some_and(|&last| (last - 1.).abs() < f64::EPSILON));
+    let mut trimmed = fixture::triples().breadth_first();
+    trimmed.tail_mut().prune(&|&first| first != 2);
+    trimmed.truncate_axis::<2>(1);
+    let trimmed_fractions: Vec<f64> = trimmed.progress().map(|(fraction, _)| fraction).collect();
+    assert_eq!(trimmed_fractions.len(), 6);
+    assert!(trimmed_fractions
+        .last()
+        .is_some_and(|&last| (last - 1.).abs() < f64::EPSILON));
+}
+
+#[test]
+fn estimate_cache_bytes() {
+    let pulled = ::core::cell::Cell::new(0_usize);
+    let counted = [0_u64, 1, 2]
+        .into_iter()
+        .inspect(|_| pulled.set(pulled.get() + 1));
+    let iter = ([0_u64, 1].into_iter(), counted).breadth_first(); // `Range<u64>` isn't `ExactSizeIterator`
+    assert_eq!(iter.estimate_cache_bytes(), 5 * 8);
+    assert_eq!(pulled.get(), 0);
+}
+
+#[test]
+fn rev_levels_weighted() {
+    let iter = BreadthFirstManager::with_weights(
+        ([0_u8, 1, 2].into_iter(), [0_u8, 1, 2].into_iter()).unflatten(),
+        [2, 1],
+    );
+    let reversed: Vec<_> = iter.rev_levels().map(|(&a, &b)| (a, b)).collect();
+    assert_eq!(reversed.len(), 9);
+    assert_eq!(reversed.first(), Some(&(2, 2)));
+    assert_eq!(reversed.get(1), Some(&(2, 1)));
+    assert!(reversed
+        .windows(2)
+        .all(|pair| matches!(pair, &[(a, b), (c, d)] if 2 * a + b >= 2 * c + d)));
+}
+
+#[test]
+fn rev_levels() {
+    let iter = fixture::triples().breadth_first();
+    let reversed: Vec<_> = iter.rev_levels().collect();
+    assert_eq!(reversed.len(), 27);
+    assert_eq!(reversed.first(), Some(&(&2, &2, &2)));
+    assert_eq!(reversed.get(1), Some(&(&1, &2, &2)));
+    assert_eq!(reversed.last(), Some(&(&0, &0, &0)));
+    let sums: Vec<_> = reversed.iter().map(|&(&a, &b, &c)| a + b + c).collect();
+    assert!(sums.windows(2).all(|pair| pair.first() >= pair.last()));
+}
+
+#[test]
+fn enumerate_combinations() {
+    let iter = fixture::triples().breadth_first();
+    let enumerated: Vec<_> = iter.enumerate_combinations().collect();
+    assert_eq!(enumerated.first(), Some(&(0, (&0, &0, &0))));
+    assert_eq!(enumerated.last(), Some(&(26, (&2, &2, &2))));
+    assert!(enumerated
+        .iter()
+        .enumerate()
+        .all(|(index, &(step, _))| index == step));
+}
+
+#[test]
+fn adjacent_pairs() {
+    let iter = fixture::triples().breadth_first();
+    let pairs: Vec<_> = iter.adjacent_pairs().collect();
+    assert_eq!(pairs.len(), 26);
+    assert_eq!(pairs.first(), Some(&((&0, &0, &0), (&0, &0, &1))));
+    assert!(pairs.windows(2).all(|window| match *window {
+        [(_, shared), (also_shared, _)] => shared == also_shared,
+        _ => false,
+    }));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_by_axis0() {
+    use rayon::iter::ParallelIterator;
+    let (first, second, third) = fixture::triples();
+    let mut parallel: Vec<(u8, u8, u8)> =
+        crate::parallel::par_by_axis0(first, (second, third)).collect();
+    parallel.sort_unstable();
+    let sequential = fixture::triples().breadth_first();
+    let mut expected: Vec<_> = sequential.owned().collect();
+    expected.sort_unstable();
+    assert_eq!(parallel, expected);
+}
+
+#[test]
+fn hashable_state() {
+    use ::core::hash::{Hash, Hasher};
+    let hash = |base_case: &BaseCase| {
+        let mut hasher = ::std::collections::hash_map::DefaultHasher::new();
+        base_case.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(&BaseCase::default()), hash(&BaseCase::default()));
+    let iter = fixture::triples().breadth_first();
+    let mut states = ::std::collections::HashSet::new();
+    assert_eq!(iter.next(), Some((&0, &0, &0)));
+    let first = iter.state();
+    assert_eq!(first.index_sum, Some(0));
+    assert_eq!(first.indices, (0, (0, (0, ()))));
+    assert!(states.insert(first.clone()));
+    assert_eq!(iter.next(), Some((&0, &0, &1)));
+    let second: State<_> = iter.state();
+    assert_eq!(second.index_sum, Some(1));
+    assert_eq!(second.indices, (0, (0, (1, ()))));
+    assert!(states.insert(second));
+    assert!(!states.insert(first));
+    assert_eq!(states.len(), 2);
+}
+
+#[test]
+fn lending() {
+    use crate::streaming::LendingBreadthFirst;
+    let indices = 0..2_u8;
+    let mut iter = (indices.clone(), indices).breadth_first();
+    let mut seen = vec![];
+    while let Some((a, b)) = LendingBreadthFirst::next(&mut iter) {
+        seen.push((*a, *b));
+    }
+    assert_eq!(seen, [(0, 0), (0, 1), (1, 0), (1, 1)]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owning() {
+    use crate::streaming::{IntoOwning, LendingBreadthFirst};
+    fn grid() -> <(Vec<u8>, Vec<char>) as IntoOwning>::Owning {
+        (vec![0, 1], vec!['a', 'b']).into_owning()
+    }
+    let mut iter = grid();
+    let mut seen = vec![];
+    while let Some((a, b)) = LendingBreadthFirst::next(&mut iter) {
+        seen.push((*a, *b));
+    }
+    assert_eq!(seen, [(0, 'a'), (0, 'b'), (1, 'a'), (1, 'b')]);
+}
+
+#[test]
+fn lexicographic() {
+    let iter = (0..3_u8, 4..=7_u8, 2..4_u8).breadth_first();
+    let sorted: Vec<_> = iter.lexicographic().collect();
+    assert_eq!(sorted.len(), 24);
+    assert!(sorted.windows(2).all(|pair| pair.first() < pair.last()));
+    assert_eq!(sorted.first(), Some(&(&0, &4, &2)));
+    assert_eq!(sorted.get(1), Some(&(&0, &4, &3)));
+    assert_eq!(sorted.last(), Some(&(&2, &7, &3)));
+    assert_eq!(iter.next(), Some((&0, &4, &2)));
+}
+
+#[test]
+fn sorted_extrema() {
+    let axes = (0..3_u8, 4..=7_u8, 2..4_u8);
+    let brute_force = axes.clone().breadth_first();
+    let all: Vec<_> = ::core::iter::from_fn(|| brute_force.next()).collect();
+    let iter = axes.breadth_first();
+    assert_eq!(iter.min_combination(), all.iter().copied().min());
+    assert_eq!(iter.max_combination(), all.iter().copied().max());
+    assert_eq!(
+        iter.minmax_combination(),
+        all.first().copied().zip(all.last().copied())
+    );
+    // Nothing was consumed:
+    assert_eq!(iter.next(), Some((&0, &4, &2)));
+    assert_eq!((0..0_u8, 0..3_u8).breadth_first().min_combination(), None);
+}
+
+#[test]
+fn extremum_by_key() {
+    use crate::monotonic::Monotonic;
+    let axes = (0..3_u8, 4..=7_u8, 2..4_u8);
+    let sum = |&(&a, &b, &c): &(&u8, &u8, &u8)| a + b + c;
+    let iter = axes.clone().breadth_first();
+    assert_eq!(
+        iter.extremum_by_key(Monotonic::Increasing, sum),
+        Some(((&0, &4, &2), (&2, &7, &3))),
+    );
+    // Nothing was consumed
+    assert_eq!(iter.next(), Some((&0, &4, &2)));
+    // Broken promise: falls back to a scan over every combination, even those already visited
+    let scanned = axes.breadth_first();
+    assert_eq!(scanned.next(), Some((&0, &4, &2)));
+    assert_eq!(
+        scanned.extremum_by_key(Monotonic::Decreasing, sum),
+        Some(((&0, &4, &2), (&2, &7, &3))),
+    );
+    assert_eq!(scanned.next(), Some((&0, &4, &3)));
+}
+
+#[test]
+fn breadth_first_ref() {
+    let axes = (0..2_u8, 0..2_u8);
+    let iter = axes.breadth_first_ref();
+    assert_eq!(iter.next(), Some((&0, &0)));
+    assert_eq!(axes.0.len(), 2); // still usable
+    assert_eq!(axes.breadth_first().owned().count(), 4);
+}
+
+#[test]
+fn ascending_visits_usize_max() {
+    let mut schedule = crate::Ascending(Some(usize::MAX - 1));
+    assert_eq!(schedule.next(), Some(usize::MAX - 1));
+    assert_eq!(schedule.next(), Some(usize::MAX));
+    assert_eq!(schedule.next(), None);
+    assert_eq!(schedule.next(), None);
+}
+
+#[test]
+fn schedule_over() {
+    let finite = (0..2_u8, 0..2_u8).breadth_first();
+    assert_eq!(finite.owned().count(), 4);
+    assert!(!finite.schedule_over());
+    let infinite = ::core::iter::repeat(());
+    let capped = BreadthFirstManager::with_schedule(
+        (infinite.clone(), infinite).unflatten(),
+        (0..=u8::MAX).map(usize::from),
+    );
+    assert_eq!(capped.owned().count(), 32_896); // 1 + 2 + ... + 256
+    assert!(capped.schedule_over());
+}
+
+#[test]
+fn flat_zip() {
+    let iter = crate::dependent::breadth_first_flat_zip(0..4_u8, |&a| 0..a);
+    let pairs: Vec<_> = ::core::iter::from_fn(|| iter.next()).collect();
+    assert_eq!(
+        pairs,
+        [(&1, &0), (&2, &0), (&2, &1), (&3, &0), (&3, &1), (&3, &2)],
+    );
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn with_dependent_last() {
+    let indices = 0..3_u8;
+    let zip = (indices.clone(), indices).breadth_first();
+    let iter = zip.with_dependent_last(|&(&a, &b)| 0..=a.saturating_add(b));
+    let mut count = 0_usize;
+    let mut last_index_sum = 0;
+    while let Some((&(&a, &b), &c)) = iter.next() {
+        assert!(c <= a.saturating_add(b));
+        let index_sum = a.saturating_add(b).saturating_add(c);
+        assert!(index_sum >= last_index_sum, "Index sums out of order");
+        last_index_sum = index_sum;
+        count = count.saturating_add(1);
+    }
+    assert_eq!(count, 27); // sum over (a, b) of a + b + 1
+                           // No weight of 1, so the earlier iterators skip odd index sums, but the last one still fills them in
+    let weighted = BreadthFirstManager::with_weights((0..3_u8, 0..3_u8).unflatten(), [2, 2]);
+    let dependent = weighted.with_dependent_last(|&(&a, &b)| 0..=a.saturating_add(b));
+    let mut index_sums = vec![];
+    while let Some((&(&a, &b), &c)) = dependent.next() {
+        index_sums.push(2 * (a + b) + c);
+    }
+    assert_eq!(index_sums.len(), 27);
+    assert!(index_sums.is_sorted());
+}
+
+#[test]
+fn const_axes() {
+    use crate::construction::ConstAxis;
+    const DIGITS: &[u8] = &[0, 1, 2];
+    const AXES: (ConstAxis<'static, u8>, [char; 2]) = (ConstAxis::new(DIGITS), ['a', 'b']);
+    let iter = AXES.breadth_first();
+    let expected = (0..3_u8, ['a', 'b'].into_iter()).breadth_first();
+    while let Some((&&digit, &letter)) = iter.next() {
+        assert_eq!(Some((&digit, &letter)), expected.next());
+    }
+    assert_eq!(expected.next(), None);
+}
+
+#[test]
+fn eq_remaining() {
+    let indices = 0..3_u8;
+    let iter = (indices.clone(), indices.clone()).breadth_first();
+    assert_eq!(iter.owned().count(), 9);
+    iter.reset();
+    let fresh = (indices.clone(), indices.clone()).breadth_first();
+    assert!(iter.eq_remaining(&fresh));
+    iter.reset();
+    let different = (indices, 0..2_u8).breadth_first();
+    assert!(!iter.eq_remaining(&different));
+}
+
+#[test]
+fn with_cancel() {
+    use ::core::sync::atomic::{AtomicBool, Ordering};
+    let cancel = AtomicBool::new(false);
+    let indices = 0..3_u8;
+    let iter = (indices.clone(), indices).breadth_first();
+    let mut cancellable = iter.with_cancel(&cancel);
+    assert_eq!(cancellable.next(), Some((&0, &0)));
+    assert_eq!(cancellable.next(), Some((&0, &1)));
+    cancel.store(true, Ordering::Relaxed);
+    assert_eq!(cancellable.next(), None);
+    assert_eq!(cancellable.next(), None);
+}
+
+#[test]
+fn last() {
+    let iter = fixture::triples().breadth_first();
+    assert_eq!(iter.last(), Some((&2, &2, &2)));
+    assert_eq!(iter.next(), Some((&0, &0, &0)));
+    assert_eq!(iter.owned().last(), Some((2, 2, 2)));
+}
+
+#[test]
+fn named_type() {
+    struct Search {
+        zip: crate::BreadthFirstZipIter<'static, (Range<u8>, Range<u8>, Range<u8>)>,
+    }
+    let search = Search {
+        zip: fixture::triples().breadth_first(),
+    };
+    assert_eq!(search.zip.next(), Some((&0, &0, &0)));
+}
+
+#[test]
+fn from_state() {
+    let axes = (0..3_u8, 0..3_u8, 0..3_u8);
+    for skip in 0..=27_usize {
+        let original = axes.clone().breadth_first();
+        assert_eq!(original.owned().take(skip).count(), skip);
+        let token = original.state();
+        let rest: Vec<_> = original.owned().collect();
+        let resumed = BreadthFirstManager::from_state(axes.clone().unflatten(), token);
+        assert_eq!(resumed.owned().collect::<Vec<_>>(), rest);
+        assert_eq!(rest.len(), 27 - skip);
+    }
+}
+
+#[test]
+fn sum_component() {
+    let iter = fixture::triples().breadth_first();
+    let sum: u8 = iter.sum_component::<1, _>();
+    assert_eq!(sum, 27); // each of 0, 1, and 2 shows up in the middle 9 times
+}
+
+#[test]
+fn random_access() {
+    let iter = fixture::triples().breadth_first();
+    assert_eq!(iter.at(&[1, 2, 0]), Some((&1, &2, &0)));
+    assert_eq!(iter.at(&[1, 3, 0]), None);
+    assert_eq!(iter.at(&[1, 2]), None);
+    assert_eq!(iter.at(&[1, 2, 0, 0]), None);
+    assert_eq!(iter.next(), Some((&0, &0, &0)));
+}
+
+#[test]
+fn builder() {
+    use crate::construction::BreadthFirstBuilder;
+    let built = BreadthFirstBuilder::new()
+        .push_axis(0..2_u8)
+        .push_axis(0..3_u8)
+        .push_axis(0..4_u8)
+        .build();
+    let zipped = (0..2_u8, 0..3_u8, 0..4_u8).breadth_first();
+    assert!(built.eq_remaining(&zipped));
+}
+
+#[test]
+fn map_axis_builder() {
+    use crate::construction::BreadthFirstBuilder;
+    let indices = || 0..3_u8;
+    let built = BreadthFirstBuilder::new()
+        .push_axis(indices())
+        .push_axis(indices())
+        .push_axis(indices())
+        .map_axis::<1, _>(|x| x * 2)
+        .build();
+    let zipped = (indices(), indices().map(|x| x * 2), indices()).breadth_first();
+    assert!(built.eq_remaining(&zipped));
+    built.reset();
+    assert_eq!(built.owned().last(), Some((2, 4, 2)));
+}
+
+#[test]
+fn build_nested() {
+    use crate::{construction::build_nested, BreadthFirstZipIter};
+    type Axis = Range<u8>;
+    let axes = || [0..2_u8, 0..3_u8, 0..4_u8];
+    let built: BreadthFirstZipIter<(Axis, Axis, Axis)> = build_nested(axes()).unwrap();
+    assert!(built.eq_remaining(&(0..2_u8, 0..3_u8, 0..4_u8).breadth_first()));
+    let too_few: Option<BreadthFirstZipIter<(Axis, Axis, Axis, Axis)>> = build_nested(axes());
+    assert!(too_few.is_none());
+    let too_many: Option<BreadthFirstZipIter<(Axis, Axis)>> = build_nested(axes());
+    assert!(too_many.is_none());
+}
+
+#[test]
+fn filter_by_component() {
+    let iter = fixture::triples().breadth_first();
+    let retained: Vec<_> = iter
+        .filter_by_component::<1, _>(|&&x| x.is_multiple_of(2))
+        .collect();
+    let brute_force = fixture::triples().breadth_first();
+    let filtered: Vec<_> = ::core::iter::from_fn(|| brute_force.next())
+        .filter(|&(_, &x, _)| x.is_multiple_of(2))
+        .collect();
+    assert_eq!(retained, filtered);
+    assert_eq!(retained.len(), 18);
+}
+
+#[test]
+fn prune() {
+    let mut iter = fixture::triples().breadth_first();
+    iter.tail_mut().prune(&|&first| first != 2);
+    iter.tail_mut().tail_mut().prune(&|&second| second != 0);
+    let pruned: Vec<_> = iter.owned().collect();
+    assert_eq!(pruned.len(), 12);
+    assert!(pruned
+        .iter()
+        .all(|&(first, second, _)| first != 2 && second != 0));
+}
+
+#[test]
+fn prune_with_captured_bound() {
+    let bound = 2_u8;
+    let below_bound = |&first: &u8| first < bound;
+    let mut iter = (0..4_u8, 0..3_u8).breadth_first();
+    iter.tail_mut().prune(&below_bound);
+    let pruned: Vec<_> = iter.owned().collect();
+    assert_eq!(pruned.len(), 6);
+    assert!(pruned.iter().all(|&(first, _)| first < bound));
+}
+
+#[test]
+fn truncate_axis() {
+    let mut iter = fixture::triples().breadth_first();
+    iter.truncate_axis::<0>(2);
+    let truncated: Vec<_> = iter.owned().collect();
+    assert_eq!(truncated.len(), 18);
+    assert!(truncated.iter().all(|&(first, _, _)| first != 2));
+    iter.reset();
+    assert_eq!(iter.owned().count(), 18);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn debug_table() {
+    let table = fixture::triples().breadth_first().debug_table();
+    let mut lines = table.lines();
+    assert_eq!(lines.next(), Some("index sum | #0 | #1 | #2"));
+    assert_eq!(lines.next(), Some("        0 |  0 |  0 |  0"));
+    assert_eq!(lines.count(), 26);
+}
+
+#[test]
+fn split_at_level() {
+    use ::std::collections::HashSet;
+    let axes = fixture::triples();
+    let (lower, upper) = axes.clone().split_at_level(3);
+    let below: Vec<_> = lower.owned().collect();
+    let above: Vec<_> = upper.owned().collect();
+    assert!(below.iter().all(|&(a, b, c)| a + b + c < 3));
+    assert!(above.iter().all(|&(a, b, c)| a + b + c >= 3));
+    let halves: HashSet<_> = below.into_iter().chain(above).collect();
+    let whole: HashSet<_> = axes.breadth_first().owned().collect();
+    assert_eq!(halves, whole);
+    assert_eq!(halves.len(), 27);
+}
+
+#[test]
+fn rfind() {
+    let iter = fixture::triples().breadth_first();
+    let sums_to = |target| move |&(a, b, c): &(&u8, &u8, &u8)| a + b + c == target;
+    assert_eq!(iter.rfind(sums_to(4)), Some((&2, &2, &0)));
+    assert_eq!(iter.rposition(|triple| sums_to(4)(&triple)), Some(22));
+    assert_eq!(iter.rfind(sums_to(7)), None);
+    iter.reset();
+    assert_eq!(
+        iter.owned().position(|triple| triple == (2, 2, 0)),
+        Some(22)
+    );
+}
+
+#[test]
+fn rfind_pruned() {
+    let mut iter = fixture::triples().breadth_first();
+    iter.tail_mut().prune(&|&first| first != 2);
+    let sums_to = |target| move |&(a, b, c): &(&u8, &u8, &u8)| a + b + c == target;
+    assert_eq!(iter.rfind(sums_to(3)), Some((&1, &2, &0)));
+    assert_eq!(iter.rposition(|triple| sums_to(3)(&triple)), Some(13));
+    iter.reset();
+    assert_eq!(
+        iter.owned().position(|triple| triple == (1, 2, 0)),
+        Some(13)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn group_by_prefix() {
+    let iter = fixture::triples().breadth_first();
+    let groups = iter.group_by_prefix::<2>();
+    assert_eq!(groups.len(), 9);
+    assert_eq!(groups.first(), Some(&((&0, &0), vec![(&0,), (&1,), (&2,)])));
+    assert!(groups.iter().all(|group| group.1.len() == 3));
+    assert_eq!(
+        groups.iter().map(|&(prefix, _)| prefix).collect::<Vec<_>>(),
+        [
+            (0, 0),
+            (0, 1),
+            (1, 0),
+            (0, 2),
+            (1, 1),
+            (2, 0),
+            (1, 2),
+            (2, 1),
+            (2, 2)
+        ]
+        .iter()
+        .map(|pair| (&pair.0, &pair.1))
+        .collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn padded() {
+    let iter = crate::construction::breadth_first_zip_padded(
+        ([1_u8, 2].into_iter(), [10_u8, 20, 30].into_iter()),
+        (0, 0),
+    );
+    let padded: Vec<_> = iter.owned().collect();
+    assert_eq!(padded.len(), 9);
+    assert_eq!(padded.last(), Some(&(0, 30)));
+    assert_eq!(padded.iter().filter(|&&(short, _)| short == 0).count(), 3);
+}
+
+#[test]
+fn lazy_axis() {
+    use crate::construction::LazyAxis;
+    use ::core::cell::Cell;
+    let calls = Cell::new(0_usize);
+    let squares = LazyAxis::from_index_fn(|index: usize| {
+        calls.set(calls.get() + 1);
+        (index < 4).then(|| index * index)
+    });
+    let iter = (0..2_usize, squares).breadth_first();
+    let combinations: Vec<_> = iter.owned().collect();
+    assert_eq!(combinations.len(), 8);
+    assert_eq!(combinations.last(), Some(&(1, 9)));
+    assert_eq!(calls.get(), 5); // Four squares, then the `None` ending the axis, each exactly once
+    iter.reset();
+    assert_eq!(iter.owned().count(), 8);
+    assert_eq!(calls.get(), 5);
+}
+
+#[test]
+fn scan_with_level() {
+    let iter = fixture::triples().breadth_first();
+    let counts = iter
+        .scan_with_level(vec![], |counts: &mut Vec<usize>, level, _| {
+            if counts.len() <= level {
+                counts.push(0);
+            }
+            *counts.last_mut()? += 1;
+            Some(counts.clone())
+        })
+        .last();
+    assert_eq!(counts, Some(vec![1, 3, 6, 7, 6, 3, 1]));
+}
+
+#[test]
+fn by_priority() {
+    use crate::ordering::{IndexSum, MaxIndex};
+    use ::std::collections::HashSet;
+    let iter = fixture::triples().breadth_first();
+    let by_sum: Vec<_> = iter.by_priority::<IndexSum>().collect();
+    let by_max: Vec<_> = iter.by_priority::<MaxIndex>().collect();
+    assert_eq!(
+        by_sum,
+        ::core::iter::from_fn(|| iter.next()).collect::<Vec<_>>()
+    );
+    assert_eq!(by_max.len(), 27);
+    assert_eq!(
+        by_max.iter().collect::<HashSet<_>>(),
+        by_sum.iter().collect::<HashSet<_>>(),
+    );
+    assert_ne!(by_max, by_sum);
+    let maxima: Vec<_> = by_max.iter().map(|&(a, b, c)| *a.max(b).max(c)).collect();
+    assert!(maxima.is_sorted());
+    assert_eq!(maxima.partition_point(|&max| max <= 1), 8);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn level_counts() {
+    let iter = (
+        [0_u8, 1, 2].into_iter(),
+        [0_u8, 1, 2].into_iter(),
+        [0_u8, 1, 2].into_iter(),
+    )
+        .breadth_first();
+    assert_eq!(iter.level_counts(), [1, 3, 6, 7, 6, 3, 1]);
+    let ragged = ([0_u8, 1, 2].into_iter(), [0_u8, 1].into_iter()).breadth_first();
+    assert_eq!(ragged.level_counts(), [1, 2, 2, 1]);
+    let empty = ([0_u8, 1].into_iter(), [0_u8; 0].into_iter()).breadth_first();
+    assert!(empty.level_counts().is_empty());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn level_counts_weighted() {
+    let mut iter = BreadthFirstManager::with_weights(
+        ([0_u8, 1, 2].into_iter(), [0_u8, 1, 2].into_iter()).unflatten(),
+        [2, 1],
+    );
+    assert_eq!(iter.level_counts(), [1, 1, 2, 1, 2, 1, 1]);
+    iter.tail_mut().prune(&|&first| first != 1);
+    assert_eq!(iter.level_counts(), [1, 1, 2, 1, 1]);
+    iter.truncate_axis::<1>(2);
+    assert_eq!(iter.level_counts(), [1, 1, 1, 1]);
+    assert_eq!(iter.owned().count(), 4);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn level_positions() {
+    let iter = (
+        [0_u8, 1, 2].into_iter(),
+        [0_u8, 1, 2].into_iter(),
+        [0_u8, 1, 2].into_iter(),
+    )
+        .breadth_first();
+    assert_eq!(iter.level_of_position(10), Some(3));
+    assert_eq!(iter.first_position_of_level(3), Some(10));
+    assert_eq!(iter.level_of_position(0), Some(0));
+    assert_eq!(iter.level_of_position(9), Some(2));
+    assert_eq!(iter.level_of_position(26), Some(6));
+    assert_eq!(iter.level_of_position(27), None);
+    assert_eq!(iter.first_position_of_level(6), Some(26));
+    assert_eq!(iter.first_position_of_level(7), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn level_tokens() {
+    let zip = || {
+        (
+            [0_u8, 1, 2].into_iter(),
+            [0_u8, 1].into_iter(),
+            [0_u8, 1, 2, 3].into_iter(),
+        )
+    };
+    let tokens = zip().breadth_first().level_tokens();
+    assert_eq!(tokens.len(), 7);
+    let mut seen = ::alloc::collections::BTreeSet::new();
+    for token in tokens {
+        let worker = zip().breadth_first();
+        let processed = worker.process_level(token, |(&a, &b, &c)| {
+            assert_eq!(usize::from(a + b + c), token.index_sum);
+            assert!(seen.insert((a, b, c)), "Processed a combination twice");
+        });
+        assert_eq!(processed, token.count);
+        assert_eq!(worker.next(), Some((&0, &0, &0)));
+    }
+    assert_eq!(seen.len(), 24);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn level_tokens_weighted() {
+    let zip = || {
+        BreadthFirstManager::with_weights(
+            ([0_u8, 1, 2].into_iter(), [0_u8, 1, 2].into_iter()).unflatten(),
+            [2, 1],
+        )
+    };
+    let tokens = zip().level_tokens();
+    assert_eq!(tokens.len(), 7);
+    let mut seen = ::alloc::collections::BTreeSet::new();
+    for token in tokens {
+        let worker = zip();
+        let processed = worker.process_level(token, |(&a, &b)| {
+            assert_eq!(usize::from(2 * a + b), token.index_sum);
+            assert!(seen.insert((a, b)), "Processed a combination twice");
+        });
+        assert_eq!(processed, token.count);
+    }
+    assert_eq!(seen.len(), 9);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn skip_combinations() {
+    let triples = || {
+        (
+            [0_u8, 1, 2].into_iter(),
+            [0_u8, 1, 2].into_iter(),
+            [0_u8, 1, 2].into_iter(),
+        )
+            .breadth_first()
+    };
+    let fresh = triples();
+    assert_eq!(fresh.skip_combinations(10), 10);
+    assert_eq!(fresh.next(), Some((&0, &1, &2)));
+    let started = triples();
+    assert_eq!(started.next(), Some((&0, &0, &0)));
+    assert_eq!(started.next(), Some((&0, &0, &1)));
+    assert_eq!(started.skip_combinations(8), 8);
+    assert_eq!(started.next(), Some((&0, &1, &2)));
+    assert_eq!(started.skip_combinations(100), 16);
+    assert_eq!(started.next(), None);
+    let exact = triples();
+    assert_eq!(exact.skip_combinations(12), 12);
+    assert_eq!(exact.next(), Some((&1, &0, &2)));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn skip_combinations_weighted() {
+    let pairs = || {
+        BreadthFirstManager::with_weights(
+            ([0_u8, 1, 2].into_iter(), [0_u8, 1, 2].into_iter()).unflatten(),
+            [2, 1],
+        )
+    };
+    let fresh = pairs();
+    assert_eq!(fresh.skip_combinations(3), 3);
+    assert_eq!(fresh.next(), Some((&1, &0)));
+    assert_eq!(fresh.skip_combinations(2), 2);
+    assert_eq!(fresh.next(), Some((&2, &0)));
+    assert_eq!(fresh.skip_combinations(100), 2);
+    assert_eq!(fresh.next(), None);
+    let mut pruned = pairs();
+    pruned.tail_mut().prune(&|&first| first != 1);
+    assert_eq!(pruned.skip_combinations(4), 4);
+    assert_eq!(pruned.next(), Some((&2, &1)));
+    // No weight of 1, so odd index sums have no combinations
+    let gapped = BreadthFirstManager::with_weights(fixture::triples().unflatten(), [2, 2, 2]);
+    assert_eq!(gapped.next(), Some((&0, &0, &0)));
+    assert_eq!(gapped.skip_combinations(3), 3);
+    assert_eq!(gapped.next(), Some((&0, &0, &2)));
+    assert_eq!(gapped.skip_combinations(100), 22);
+    assert_eq!(gapped.next(), None);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn partition_combinations() {
+    let iter = fixture::triples().breadth_first();
+    let (even, odd) = iter.partition_combinations(|&(a, b, c)| (a + b + c).is_multiple_of(2));
+    assert_eq!(even.len(), 14);
+    assert_eq!(odd.len(), 13);
+    assert_eq!(even.first(), Some(&(&0, &0, &0)));
+    assert_eq!(odd.first(), Some(&(&0, &0, &1)));
+    assert!(odd.iter().all(|&(a, b, c)| !(a + b + c).is_multiple_of(2)));
+}
+
+#[test]
+fn rewind_is_idempotent() {
+    let expected: Vec<_> = fixture::triples().breadth_first().owned().collect();
+    let iter = fixture::triples().breadth_first();
+    iter.tail.rewind();
+    iter.tail.rewind();
+    iter.tail.rewind();
+    assert_eq!(iter.owned().collect::<Vec<_>>(), expected);
+    iter.reset();
+    assert_eq!(iter.owned().take(10).count(), 10);
+    iter.reset();
+    iter.tail.rewind();
+    iter.reset();
+    assert_eq!(iter.owned().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+#[allow(clippy::std_instead_of_alloc)] // `alloc` isn't linked in every configuration
+fn map_collect() {
+    use ::std::collections::BTreeSet;
+    let iter = fixture::triples().breadth_first();
+    let sums: BTreeSet<u8> = iter.map_collect(|(a, b, c)| a + b + c);
+    assert_eq!(sums, (0..=6).collect());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn shuffled_within_level() {
+    let axes = fixture::triples();
+    let shuffle = |seed| -> Vec<_> {
+        axes.clone()
+            .breadth_first()
+            .shuffled_within_level(seed)
+            .map(|(&a, &b, &c)| (a, b, c))
+            .collect()
+    };
+    let (first, again, other) = (shuffle(42), shuffle(42), shuffle(43));
+    assert_eq!(first, again);
+    assert_ne!(first, other);
+    let by_level = |mut combinations: Vec<(u8, u8, u8)>| {
+        let sum = |&(a, b, c): &(u8, u8, u8)| a + b + c;
+        assert!(combinations.is_sorted_by_key(sum));
+        combinations.sort_by_key(|combination| (sum(combination), *combination));
+        combinations
+    };
+    let expected: Vec<_> = axes.breadth_first().owned().collect();
+    assert_eq!(by_level(first), expected);
+    assert_eq!(by_level(other), expected);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn take_while_level() {
+    let iter = fixture::triples().breadth_first();
+    let taken: Vec<_> = iter
+        .take_while_level(|&(a, b, c)| a + b + c != 4 && *a != 1)
+        .collect();
+    assert_eq!(taken.len(), 17); // Every combination with index sum 0 through 3, even those with `a == 1`
+    assert!(taken.iter().all(|&(a, b, c)| a + b + c <= 3));
+}
+
+#[test]
+fn flat_matches_recursive() {
+    use crate::flat::{BreadthFirstZip2, BreadthFirstZip3};
+    let triples = BreadthFirstZip3::new(0..3_u8, 0..3_u8, 0..3_u8);
+    let recursive_triples = (0..3_u8, 0..3_u8, 0..3_u8).breadth_first();
+    assert_eq!(
+        ::core::iter::from_fn(|| triples.next()).collect::<Vec<_>>(),
+        ::core::iter::from_fn(|| recursive_triples.next()).collect::<Vec<_>>(),
+    );
+    let ragged = BreadthFirstZip3::new(0..1_u8, 0..4_u8, 0..2_u8);
+    let recursive_ragged = (0..1_u8, 0..4_u8, 0..2_u8).breadth_first();
+    assert_eq!(
+        ::core::iter::from_fn(|| ragged.next()).collect::<Vec<_>>(),
+        ::core::iter::from_fn(|| recursive_ragged.next()).collect::<Vec<_>>(),
+    );
+    let pairs = BreadthFirstZip2::new(0..4_u8, 0..2_u8);
+    let recursive_pairs = (0..4_u8, 0..2_u8).breadth_first();
+    assert_eq!(
+        ::core::iter::from_fn(|| pairs.next()).collect::<Vec<_>>(),
+        ::core::iter::from_fn(|| recursive_pairs.next()).collect::<Vec<_>>(),
+    );
+    assert_eq!(BreadthFirstZip2::new(0..0_u8, 0..2_u8).next(), None);
+}
+
+#[test]
+fn owned_copy() {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Meters(u8);
+    let iter = ((0..2_u8).map(Meters), (0..2_u8).map(Meters)).breadth_first();
+    assert_eq!(
+        iter.owned().collect::<Vec<_>>(),
+        [
+            (Meters(0), Meters(0)),
+            (Meters(0), Meters(1)),
+            (Meters(1), Meters(0)),
+            (Meters(1), Meters(1)),
+        ],
+    );
+}
+
+#[test]
+fn breadth_first_zip_as() {
+    #[derive(Debug, PartialEq)]
+    struct Config {
+        width: u8,
+        height: u8,
+        depth: u8,
+    }
+    impl From<(u8, u8, u8)> for Config {
+        fn from((width, height, depth): (u8, u8, u8)) -> Self {
+            Self {
+                width,
+                height,
+                depth,
+            }
+        }
+    }
+    let indices = 0..2_u8;
+    let iter = (indices.clone(), indices.clone(), indices).breadth_first();
+    let configs: Vec<Config> = iter.breadth_first_zip_as().collect();
+    assert_eq!(configs.len(), 8);
+    assert_eq!(
+        configs.get(3),
+        Some(&Config {
+            width: 1,
+            height: 0,
+            depth: 0,
+        }),
+    );
+    assert_eq!(
+        (&1_u8, (&2_u8, (&3_u8, ()))).flatten_into::<(&u8, &u8, &u8)>(),
+        (&1, &2, &3),
+    );
+}
+
+#[test]
+fn merge_axes() {
+    use crate::construction::BreadthFirstBuilder;
+    use ::std::collections::HashSet;
+    let merged = BreadthFirstBuilder::new()
+        .push_axis(0..2_u8)
+        .push_axis(0..2_u8)
+        .push_axis(0..3_u8)
+        .merge_axes::<1>()
+        .build();
+    let pairs: Vec<_> = merged.owned().collect();
+    assert_eq!(
+        pairs.get(..3),
+        Some(&[(0, (0, 0)), (0, (0, 1)), (1, (0, 0))][..])
+    );
+    let flattened: HashSet<_> = pairs.into_iter().map(|(a, (b, c))| (a, b, c)).collect();
+    let triples: HashSet<_> = (0..2_u8, 0..2_u8, 0..3_u8)
+        .breadth_first()
+        .owned()
+        .collect();
+    assert_eq!(flattened, triples);
+    assert_eq!(flattened.len(), 12);
+}
+
+#[test]
+#[cfg(feature = "testing")]
+fn check_invariants() {
+    let report = fixture::triples().breadth_first().check_invariants();
+    assert!(report.monotonic);
+    assert_eq!(report.duplicates, 0);
+    assert_eq!(report.total, 27);
+    assert_eq!(report.max_level, Some(6));
+    let repeated = ([0_u8, 0].into_iter(), 0..2_u8)
+        .breadth_first()
+        .check_invariants();
+    assert_eq!(repeated.duplicates, 2);
+    assert_eq!(repeated.total, 4);
+}
+
+#[test]
+fn first_n_levels() {
+    let axes = fixture::triples();
+    let sampled = axes.clone().breadth_first().first_n_levels(3);
+    assert_eq!(sampled.every_nth(3).count(), 4);
+    let iter = axes.breadth_first().first_n_levels(3);
+    let first: Vec<_> = iter.owned().collect();
+    assert_eq!(first.len(), 10);
+    assert!(first.iter().all(|&(a, b, c)| a + b + c < 3));
+}
+
+#[test]
+fn lazy_construction() {
+    use ::core::cell::Cell;
+    let pulled = Cell::new(0_usize);
+    let count = |_: &u8| pulled.set(pulled.get() + 1);
+    let iter = ((0..3_u8).inspect(count), (0..3_u8).inspect(count)).breadth_first();
+    assert_eq!(pulled.get(), 0);
+    assert_eq!(iter.next(), Some((&0, &0)));
+    assert_eq!(pulled.get(), 2);
+    // Moving on to index sum 1 needs the second element of only the last iterator:
+    assert_eq!(iter.next(), Some((&0, &1)));
+    assert_eq!(pulled.get(), 3);
+    assert_eq!(iter.next(), Some((&1, &0)));
+    assert_eq!(pulled.get(), 4);
+}
+
+/// Counts how many times any instance has been cloned (per thread, so tests running in parallel don't interfere),
+/// to check that borrowing iteration hands out references into the cache instead of copies.
+#[derive(Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct CloneCounter(u8);
+
+thread_local! {
+    static CLONES: ::core::cell::Cell<usize> = const { ::core::cell::Cell::new(0) };
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl Clone for CloneCounter {
+    #[inline]
+    fn clone(&self) -> Self {
+        CLONES.with(|clones| clones.set(clones.get().saturating_add(1)));
+        Self(self.0)
+    }
+}
+
+#[test]
+fn never_clones() {
+    let axis = || (0..3_u8).map(CloneCounter);
+    let iter = (axis(), axis(), axis()).breadth_first();
+    let mut count = 0_usize;
+    while let Some((a, b, c)) = iter.next() {
+        assert!(a.0 < 3 && b.0 < 3 && c.0 < 3);
+        count += 1;
+    }
+    assert_eq!(count, 27);
+    assert_eq!(CLONES.with(::core::cell::Cell::get), 0);
+}
+
+#[test]
+fn lending_never_clones() {
+    use crate::streaming::LendingBreadthFirst;
+    let axis = || (0..3_u8).map(CloneCounter);
+    let mut iter = (axis(), axis()).breadth_first();
+    let mut count = 0_usize;
+    while let Some((a, b)) = LendingBreadthFirst::next(&mut iter) {
+        assert!(a.0 < 3 && b.0 < 3);
+        count += 1;
+    }
+    assert_eq!(count, 9);
+    assert_eq!(CLONES.with(::core::cell::Cell::get), 0);
+}
+
+#[test]
+fn random_access_and_replay_never_clone() {
+    let axis = || (0..3_u8).map(CloneCounter);
+    let iter = (axis(), axis()).breadth_first();
+    while iter.next().is_some() {}
+    iter.reset();
+    let mut count = 0_usize;
+    while iter.next().is_some() {
+        count += 1;
+    }
+    assert_eq!(count, 9);
+    assert_eq!(iter.at(&[2, 1]).map(|(a, b)| (a.0, b.0)), Some((2, 1)));
+    assert_eq!(iter.last().map(|(a, b)| (a.0, b.0)), Some((2, 2)));
+    let state = iter.state();
+    let resumed = BreadthFirstManager::from_state((axis(), axis()).unflatten(), state);
+    assert_eq!(resumed.next(), None);
+    assert_eq!(CLONES.with(::core::cell::Cell::get), 0);
+}
+
+/// Iterator over `0..3` that isn't `Clone` and counts how many times it's been pulled.
+struct CountPulls<'cell> {
+    next: u8,
+    pulls: &'cell ::core::cell::Cell<usize>,
+}
+
+#[allow(clippy::missing_trait_methods)]
+impl Iterator for CountPulls<'_> {
+    type Item = u8;
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next;
+        (current < 3).then(|| {
+            self.pulls.set(self.pulls.get().saturating_add(1));
+            self.next = current.saturating_add(1);
+            current
+        })
+    }
+}
+
+#[test]
+fn non_clone_iterators() {
+    let pulls = ::core::cell::Cell::new(0_usize);
+    let axis = || CountPulls {
+        next: 0,
+        pulls: &pulls,
+    };
+    let iter = (axis(), axis()).breadth_first();
+    assert_eq!(iter.owned().count(), 9);
+    assert_eq!(pulls.get(), 6); // each element pulled exactly once, then cached
+}
+
+#[cfg(not(feature = "external-reiterator"))]
+#[test]
+fn in_crate_reiterator() {
+    use crate::Reiterate;
+    let pulls = ::core::cell::Cell::new(0_usize);
+    let iter = (0..100_usize)
+        .inspect(|_| pulls.set(pulls.get() + 1))
+        .reiterate();
+    assert_eq!(iter.at(99), Some(&99));
+    assert!((0..100).all(|index| iter.at(index) == Some(&index)));
+    assert_eq!(iter.at(100), None);
+    assert_eq!(pulls.get(), 100);
+    iter.index.set(41);
+    assert_eq!(iter.next().map(|indexed| *indexed.value), Some(42));
+    iter.restart();
+    assert_eq!(iter.get().map(|indexed| *indexed.value), Some(0));
+}
+
+/// Deliberately broken implementation that yields a combination at index sum 1 while claiming it's still at index sum 0.
+#[cfg(debug_assertions)]
+#[derive(Default)]
+struct Broken(::core::cell::Cell<bool>);
+
+#[cfg(debug_assertions)]
+impl crate::sealed::BreadthFirst for Broken {}
+
+#[cfg(debug_assertions)]
+#[allow(clippy::missing_trait_methods)]
+impl<'item> BreadthFirst<'item> for Broken {
+    const DEPTH: usize = 0;
+    type Advance = ();
+    type Indices = ();
+    fn next(&self, index_sum: usize) -> Option<Self::Advance> {
+        (index_sum <= 1 && self.0.get()).then(|| self.0.set(false))
+    }
+    fn rewind(&self) {
+        self.0.set(true);
+    }
+    fn empty_axis(&self) -> Option<usize> {
+        None
+    }
+    fn indices(&self) -> Self::Indices {}
+    fn set_weights(&mut self, _: &[usize]) {}
+    fn restore(&self, (): Self::Indices) {}
+    fn at_indices(&self, _: &[usize]) -> Option<Self::Advance> {
+        None
+    }
+    fn last_of_each(&self) -> Option<Self::Advance> {
+        None
+    }
+    fn index_sum(&self) -> usize {
+        0
+    }
+    fn max_index_sum(&self) -> Option<usize> {
+        None
+    }
+    fn truncate_axis(&mut self, _: usize, _: usize) {}
+    fn infinite_axis(&self) -> Option<usize> {
+        None
+    }
+    fn no_combinations(&self) -> bool {
+        false
+    }
+}
+
+#[test]
+fn ordering_holds() {
+    let iter = fixture::triples().breadth_first();
+    let mut previous = 0;
+    while iter.next().is_some() {
+        let index_sum = iter.tail.index_sum();
+        assert!(index_sum >= previous);
+        previous = index_sum;
+    }
+    assert_eq!(previous, 6);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "outside the index sum")]
+fn ordering_violation() {
+    let iter = BreadthFirstManager::new(Broken::default());
+    assert_eq!(iter.next(), Some(()));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn send() {
+    const fn assert_send<T: Send>(_: &T) {}
+    let iter = fixture::triples().breadth_first();
+    assert_send(&iter);
+    let moved = ::std::thread::spawn(move || iter.owned().count());
+    assert_eq!(moved.join().ok(), Some(27));
+}
+
+#[test]
+fn fixed_axis() {
+    let iter = (0..3_u8, ::core::iter::once(7_u8), 0..2_u8).breadth_first();
+    let owned: Vec<_> = iter.owned().collect();
+    assert_eq!(owned.len(), 6);
+    assert!(owned.iter().all(|&(_, fixed, _)| fixed == 7));
+    assert_eq!(owned.get(1), Some(&(0, 7, 1)));
+}
+
+#[test]
+fn map_axis() {
+    struct NotClone(u8);
+    let factor = NotClone(2);
+    let doubled = (0..3_u8).map(move |x| x * factor.0);
+    let iter = (0..3_u8, doubled).breadth_first();
+    let owned: Vec<(u8, u8)> = iter.owned().collect();
+    assert_eq!(owned.len(), 9);
+    assert_eq!(owned.get(1), Some(&(0, 2)));
+    assert_eq!(owned.last(), Some(&(2, 4)));
+}
+
+#[test]
+fn grow_axis_by_rezipping() {
+    let mut axis = vec![0_u8, 1];
+    let before: Vec<_> = (0..2_u8, axis.iter().copied())
+        .breadth_first()
+        .owned()
+        .collect();
+    axis.push(2);
+    let after: Vec<_> = (0..2_u8, axis.iter().copied())
+        .breadth_first()
+        .owned()
+        .collect();
+    assert_eq!((before.len(), after.len()), (4, 6));
+    assert!(before.iter().all(|combination| after.contains(combination)));
+    assert!(after.contains(&(1, 2)));
+}
+
+#[test]
+fn nested_zip_axis() {
+    let inner = (0..2_u8, 0..2_u8).breadth_first();
+    let outer = (inner.owned(), 0..2_u8).breadth_first();
+    let composed: Vec<_> = outer.owned().collect();
+    assert_eq!(composed.len(), 8);
+    assert_eq!(composed.first(), Some(&((0, 0), 0)));
+    assert_eq!(composed.get(1), Some(&((0, 0), 1)));
+    assert_eq!(composed.get(2), Some(&((0, 1), 0)));
+    assert_eq!(composed.last(), Some(&((1, 1), 1)));
+}
+
+#[test]
+fn owned() {
+    let iter = fixture::triples().breadth_first();
+    let owned: Vec<(u8, u8, u8)> = iter.owned().collect();
+    assert_eq!(owned.len(), 27);
+    assert_eq!(owned.first(), Some(&(0, 0, 0)));
+    assert_eq!(owned.get(1), Some(&(0, 0, 1)));
+    assert_eq!(owned.last(), Some(&(2, 2, 2)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn collect_bounded() {
+    let iter = fixture::triples().breadth_first();
+    let all: Vec<_> = ::core::iter::from_fn(|| iter.next()).collect();
+    iter.reset();
+    let (first, exhausted) = iter.collect_bounded(5);
+    assert_eq!((first.len(), exhausted), (5, false));
+    assert!(all.starts_with(&first));
+    assert_eq!(iter.next(), all.get(5).copied());
+    iter.reset();
+    assert_eq!(iter.collect_bounded(27), (all.clone(), true));
+    iter.reset();
+    assert_eq!(iter.collect_bounded(100), (all.clone(), true));
+    iter.reset();
+    assert_eq!(iter.collect_bounded(usize::MAX), (all, true));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn index_sum_step() {
+    let iter = BreadthFirstManager::with_index_sum_step(fixture::triples().unflatten(), 2);
+    let levels: Vec<_> = iter
+        .by_index_sum()
+        .map(|(index_sum, batch)| (index_sum, batch.len()))
+        .collect();
+    assert_eq!(levels, [(0, 1), (2, 6), (4, 6), (6, 1)]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn levels_vec() {
+    let iter = fixture::triples().breadth_first();
+    let levels = iter.levels_vec();
+    assert_eq!(
+        levels.iter().map(Vec::len).collect::<Vec<_>>(),
+        [1, 3, 6, 7, 6, 3, 1]
+    );
+    assert_eq!(levels.get(6), Some(&vec![(&2, &2, &2)]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn by_index_sum() {
+    let iter = fixture::triples().breadth_first();
+    let levels: Vec<_> = iter.by_index_sum().collect();
+    assert_eq!(
+        levels
+            .iter()
+            .map(|level| (level.0, level.1.len()))
+            .collect::<Vec<_>>(),
+        [(0, 1), (1, 3), (2, 6), (3, 7), (4, 6), (5, 3), (6, 1)]
+    );
+    assert_eq!(
+        levels.get(2),
+        Some(&(
+            2,
+            vec![
+                (&0, &0, &2),
+                (&0, &1, &1),
+                (&0, &2, &0),
+                (&1, &0, &1),
+                (&1, &1, &0),
+                (&2, &0, &0)
+            ]
+        ))
+    );
+}
+
+#[test]
+fn empty_axis_position() {
+    let (empty, full) = (0..0_u8, 0..3_u8);
+    assert_eq!(
+        (empty.clone(), full.clone(), full.clone())
+            .try_breadth_first()
+            .err(),
+        Some(EmptyAxis { index: 0 })
+    );
+    assert_eq!(
+        (full.clone(), empty.clone(), full.clone())
+            .try_breadth_first()
+            .err(),
+        Some(EmptyAxis { index: 1 })
+    );
+    assert_eq!(
+        (full.clone(), full.clone(), empty)
+            .try_breadth_first()
+            .err(),
+        Some(EmptyAxis { index: 2 })
+    );
+    let iter = (full.clone(), full.clone(), full)
+        .try_breadth_first()
+        .unwrap();
+    assert_eq!(iter.next(), Some((&0, &0, &0)));
+}
+
+#[test]
+fn infinite_axis() {
+    let finite = (0..3_u8, [1_u8, 2].into_iter()).breadth_first();
+    assert!(!finite.has_infinite_axis());
+    assert_eq!(finite.require_finite().err(), None);
+    let infinite = (0..3_u8, 0_u8.., [1_u8, 2].iter().cycle()).breadth_first();
+    assert!(infinite.has_infinite_axis());
+    let Err(err) = infinite.require_finite() else {
+        panic!("Accepted an infinite axis");
+    };
+    assert_eq!(err.index, 1);
+    let mut truncated = (0..3_u8, 0_u8..).breadth_first();
+    truncated.truncate_axis::<1>(4);
+    assert!(!truncated.has_infinite_axis());
+}
+
+#[test]
+fn labeled_error() {
+    use crate::Label;
+    let ok = (0..2_u8, 0..3_u8).try_breadth_first().label("digits");
+    assert!(ok.is_ok_and(|iter| iter.next() == Some((&0, &0))));
+    let Err(err) = (0..2_u8, 0..0_u8).try_breadth_first().label("digits") else {
+        panic!("Accepted an empty axis");
+    };
+    assert_eq!(err.label, "digits");
+    assert_eq!(err.cause, EmptyAxis { index: 1 });
+    let message = err.to_string();
+    assert!(message.contains("digits"), "{message}");
+    assert!(
+        message.contains(&EmptyAxis { index: 1 }.to_string()),
+        "{message}"
+    );
+    let Err(infinite) = (0..2_u8, 0_u8..)
+        .breadth_first()
+        .require_finite()
+        .label("counter")
+    else {
+        panic!("Accepted an infinite axis");
+    };
+    assert_eq!(infinite.label, "counter");
+    assert_eq!(infinite.cause.index, 1);
+}
+
+#[test]
+fn empty_axis_is_empty() {
+    let zip = crate::breadth_first_zip3(0..3_u8, 0..0_u8, 0..3_u8);
+    assert_eq!(zip.next(), None);
+    assert_eq!(zip.owned().count(), 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn tagged() {
+    use crate::heterogeneous::IntoTagged;
+
+    #[derive(Debug, PartialEq)]
+    enum Tag {
+        Number(u8),
+        Letter(char),
+    }
+    impl IntoTagged<Tag> for u8 {
+        #[inline]
+        fn tagged(&self) -> Tag {
+            Tag::Number(*self)
+        }
+    }
+    impl IntoTagged<Tag> for char {
+        #[inline]
+        fn tagged(&self) -> Tag {
+            Tag::Letter(*self)
+        }
+    }
+
+    let iter = (0..2_u8, 'a'..='b').breadth_first();
+    assert_eq!(
+        iter.next_tagged(),
+        Some(vec![Tag::Number(0), Tag::Letter('a')])
+    );
+    assert_eq!(
+        iter.next_tagged(),
+        Some(vec![Tag::Number(0), Tag::Letter('b')])
+    );
+    assert_eq!(
+        iter.next_tagged(),
+        Some(vec![Tag::Number(1), Tag::Letter('a')])
+    );
+    assert_eq!(
+        iter.next_tagged(),
+        Some(vec![Tag::Number(1), Tag::Letter('b')])
+    );
+    assert_eq!(iter.next_tagged::<Tag>(), None);
+}
+
 #[test]
 fn reduced_qc_example() {
     #![allow(clippy::shadow_reuse, clippy::shadow_same)]
@@ -102,6 +1840,36 @@ fn reduced_qc_example() {
     );
 }
 
+#[cfg(feature = "testing")]
+#[test]
+fn assert_exhaustive() {
+    let iter = fixture::triples().breadth_first();
+    crate::testing::assert_exhaustive(&iter, &[&[0, 1, 2], &[0, 1, 2], &[0, 1, 2]]);
+}
+
+#[test]
+fn into_iterator_axes() {
+    let letters = vec!['a', 'b'];
+    let numbers = vec![0_u8, 1, 2];
+    let by_value = (letters.clone(), numbers.clone()).breadth_first();
+    let by_iter = (letters.into_iter(), numbers.into_iter()).breadth_first();
+    let mut count = 0_usize;
+    while let Some(combination) = by_value.next() {
+        assert_eq!(Some(combination), by_iter.next());
+        count += 1;
+    }
+    assert_eq!(by_iter.next(), None);
+    assert_eq!(count, 6);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+#[should_panic(expected = "Missing combinations")]
+fn assert_exhaustive_missing() {
+    let iter = (0..3_u8, 0..2_u8).breadth_first();
+    crate::testing::assert_exhaustive(&iter, &[&[0, 1, 2], &[0, 1, 2]]);
+}
+
 mod qc {
     #![allow(warnings)]
 
@@ -0,0 +1,103 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Assertions for testing your own breadth-first zips.
+
+use crate::{homogeneous::IntoArray, BreadthFirst, BreadthFirstManager, Flatten};
+use ::core::fmt::Debug;
+use alloc::{collections::BTreeSet, vec::Vec};
+
+/// Assert that the rest of this zip is exactly the cross product of `expected` (one slice per iterator, from left to right):
+/// every combination exactly once, nothing else, in any order.
+///
+/// Each slice should have no duplicates of its own.
+/// # Panics
+/// On the first combination that's repeated or isn't in the cross product, or if any combination is missing.
+#[inline]
+pub fn assert_exhaustive<'item, T: Debug + PartialEq + 'item, Tail: BreadthFirst<'item>>(
+    manager: &'item BreadthFirstManager<'item, Tail>,
+    expected: &[&[T]],
+) where
+    Tail::Advance: IntoArray,
+    <Tail::Advance as IntoArray>::Array: AsRef<[&'item T]>,
+{
+    assert_eq!(
+        expected.len(),
+        Tail::DEPTH,
+        "Expected one slice per iterator ({} iterators but {} slices)",
+        Tail::DEPTH,
+        expected.len(),
+    );
+    let mut seen: Vec<Vec<&T>> = Vec::new();
+    while let Some(array) = manager.next_array() {
+        let combination = array.as_ref().to_vec();
+        for (axis, (element, options)) in combination.iter().zip(expected).enumerate() {
+            assert!(
+                options.contains(element),
+                "Combination {combination:?} has {element:?} from iterator #{axis}, which isn't in {options:?}",
+            );
+        }
+        assert!(
+            !seen.contains(&combination),
+            "Combination {combination:?} appeared more than once",
+        );
+        seen.push(combination);
+    }
+    let total = expected
+        .iter()
+        .map(|options| options.len())
+        .try_fold(1_usize, usize::checked_mul);
+    assert_eq!(
+        Some(seen.len()),
+        total,
+        "Missing combinations: saw {} but expected {total:?}",
+        seen.len(),
+    );
+}
+
+/// What `check_invariants` found while draining a zip.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct InvariantReport {
+    /// Whether index sums never decreased from one combination to the next.
+    pub monotonic: bool,
+    /// How many combinations were equal to one that came before.
+    pub duplicates: usize,
+    /// How many combinations there were in total, duplicates included.
+    pub total: usize,
+    /// Highest index sum of any combination, or `None` if there were none.
+    pub max_level: Option<usize>,
+}
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Drain every remaining combination and report on the invariants a breadth-first zip should uphold,
+    /// instead of panicking at the first violation like `assert_exhaustive`, e.g. to test a custom schedule or weights.
+    /// Index sums are those of each combination itself (weights and all), not of the schedule.
+    #[inline]
+    #[must_use]
+    pub fn check_invariants(&'item self) -> InvariantReport
+    where
+        <Tail::Advance as Flatten>::Flattened: Ord,
+    {
+        let mut seen = BTreeSet::new();
+        let mut report = InvariantReport {
+            monotonic: true,
+            ..InvariantReport::default()
+        };
+        while let Some(combination) = self.next() {
+            let level = self.tail.index_sum();
+            report.monotonic &= report.max_level.is_none_or(|max| level >= max);
+            report.max_level = Some(report.max_level.map_or(level, |max| max.max(level)));
+            report.total = report.total.saturating_add(1);
+            if !seen.insert(combination) {
+                report.duplicates = report.duplicates.saturating_add(1);
+            }
+        }
+        report
+    }
+}
@@ -0,0 +1,217 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Breadth-first `zip` over an array (or, with `alloc`, a `Vec`) of iterators of the same type, with no limit on its length.
+
+#[cfg(feature = "alloc")]
+use crate::EmptyAxis;
+use crate::{walk, Reiterate, Reiterator};
+#[cfg(feature = "alloc")]
+use ::alloc::vec::Vec;
+use ::core::{cell::Cell, convert::Infallible, marker::PhantomData, ops::Range};
+
+/// Array of iterators that can be zipped up breadth-first, like `BreadthFirstZip` for tuples.
+pub trait BreadthFirstArrayZip<'item, Iter: Iterator, const N: usize> {
+    /// Set up a breadth-first zip over every iterator in this array.
+    #[must_use]
+    fn breadth_first(self) -> BreadthFirstArrayZipped<'item, Iter, N>;
+}
+
+impl<'item, Iter: Iterator, const N: usize> BreadthFirstArrayZip<'item, Iter, N> for [Iter; N] {
+    #[inline(always)]
+    fn breadth_first(self) -> BreadthFirstArrayZipped<'item, Iter, N> {
+        BreadthFirstArrayZipped {
+            iters: self.map(Reiterate::reiterate),
+            base_case: Cell::new(true),
+            index_sum: Cell::new(None),
+            lifetime: PhantomData,
+        }
+    }
+}
+
+/// Breadth-first zip over an array of iterators, yielding arrays in the same order as the equivalent tuple would.
+pub struct BreadthFirstArrayZipped<'item, Iter: Iterator, const N: usize> {
+    /// Enumerated caching iterators, from left to right.
+    iters: [Reiterator<Iter>; N],
+    /// Whether we can still yield at the end of the current recursion, like `BaseCase`.
+    base_case: Cell<bool>,
+    /// Maximum possible sum of indices, or `None` before the first call to `next`.
+    index_sum: Cell<Option<usize>>,
+    /// Representation of this struct's lifetime.
+    lifetime: PhantomData<&'item Infallible>,
+}
+
+impl<'item, Iter: Iterator, const N: usize> BreadthFirstArrayZipped<'item, Iter, N>
+where
+    Iter::Item: 'item,
+{
+    /// Like `Iterator::next` but with a generic lifetime, like `BreadthFirstManager::next`.
+    /// # Panics
+    /// Never: each `expect` is a sanity check on our own bookkeeping.
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    #[must_use]
+    pub fn next(&'item self) -> Option<[&'item Iter::Item; N]> {
+        step(&self.iters, &self.base_case, &self.index_sum)?;
+        Some(self.iters.each_ref().map(current))
+    }
+}
+
+/// Breadth-first zip over a number of iterators only known at runtime, yielding `Vec`s in the same order as the equivalent tuple would.
+///
+/// To mix different kinds of iterators, erase them to `Box<dyn Iterator<Item = T>>`: no `Clone` (or factory to re-create them) needed,
+/// since each element is pulled once and cached like everywhere else in this crate.
+#[cfg(feature = "alloc")]
+pub struct BreadthFirstVecZipped<'item, Iter: Iterator> {
+    /// Enumerated caching iterators, from left to right.
+    iters: Vec<Reiterator<Iter>>,
+    /// Whether we can still yield at the end of the current recursion, like `BaseCase`.
+    base_case: Cell<bool>,
+    /// Maximum possible sum of indices, or `None` before the first call to `next`.
+    index_sum: Cell<Option<usize>>,
+    /// Representation of this struct's lifetime.
+    lifetime: PhantomData<&'item Infallible>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'item, Iter: Iterator> BreadthFirstVecZipped<'item, Iter>
+where
+    Iter::Item: 'item,
+{
+    /// Set up a breadth-first zip over every iterator `axes` yields, checking up front that none of them is empty.
+    /// # Errors
+    /// If any iterator is empty, reporting the position of the first one that is.
+    #[inline]
+    pub fn try_from_iter<Axes: IntoIterator<Item = Iter>>(axes: Axes) -> Result<Self, EmptyAxis> {
+        let iters: Vec<_> = axes.into_iter().map(Reiterate::reiterate).collect();
+        if let Some(index) = iters.iter().position(|iter| iter.get().is_none()) {
+            return Err(EmptyAxis { index });
+        }
+        Ok(Self {
+            iters,
+            base_case: Cell::new(true),
+            index_sum: Cell::new(None),
+            lifetime: PhantomData,
+        })
+    }
+    /// Like `Iterator::next` but with a generic lifetime, like `BreadthFirstManager::next`.
+    /// # Panics
+    /// Never: each `expect` is a sanity check on our own bookkeeping.
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    #[must_use]
+    pub fn next(&'item self) -> Option<Vec<&'item Iter::Item>> {
+        step(&self.iters, &self.base_case, &self.index_sum)?;
+        Some(self.iters.iter().map(current).collect())
+    }
+}
+
+/// Breadth-first zip over slices by index, yielding references straight into each slice:
+/// no cache, no cloning, and no `&&T` like zipping `slice::iter` would give.
+pub struct IndexedSlices<'item, T, const N: usize> {
+    /// Slices to index into.
+    slices: [&'item [T]; N],
+    /// Breadth-first zip over valid indices into each slice.
+    indices: BreadthFirstArrayZipped<'item, Range<usize>, N>,
+}
+
+impl<'item, T, const N: usize> IndexedSlices<'item, T, N> {
+    /// Like `Iterator::next` but with a generic lifetime, like `BreadthFirstManager::next`.
+    /// # Panics
+    /// Never: each `expect` is a sanity check on our own bookkeeping.
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    #[must_use]
+    pub fn next(&'item self) -> Option<[&'item T; N]> {
+        let mut indices = self.indices.next()?.into_iter();
+        Some(self.slices.map(|slice| {
+            indices
+                .next()
+                .and_then(|&index| slice.get(index))
+                .expect("Internal error: index out of bounds")
+        }))
+    }
+}
+
+/// Breadth-first zip over slices by index, in the same order as zipping their iterators.
+#[inline]
+#[must_use]
+pub fn breadth_first_slices<'item, T, const N: usize>(
+    slices: [&'item [T]; N],
+) -> IndexedSlices<'item, T, N> {
+    IndexedSlices {
+        slices,
+        indices: slices.map(|slice| 0..slice.len()).breadth_first(),
+    }
+}
+
+/// Move each iterator into position for the next combination, moving on to the next index sum whenever this one runs out,
+/// or `None` once we've run out of combinations. Shared by every zip in this module, whatever holds its iterators.
+#[cfg_attr(not(feature = "alloc"), allow(clippy::single_call_fn))] // Also used by the `Vec` zip, with `alloc`
+#[inline]
+fn step<Iter: Iterator>(
+    iters: &[Reiterator<Iter>],
+    base_case: &Cell<bool>,
+    index_sum: &Cell<Option<usize>>,
+) -> Option<()> {
+    if index_sum
+        .get()
+        .and_then(|current| advance(iters, base_case, current))
+        .is_none()
+    {
+        let next = index_sum
+            .get()
+            .map_or(Some(0), |current| current.checked_add(1))?;
+        index_sum.set(Some(next));
+        rewind(iters, base_case);
+        advance(iters, base_case, next)?;
+    }
+    Some(())
+}
+
+/// Element an iterator is currently on, right after a successful `step`.
+/// # Panics
+/// Never: this is a sanity check on our own bookkeeping.
+#[cfg_attr(not(feature = "alloc"), allow(clippy::single_call_fn))] // Also used by the `Vec` zip, with `alloc`
+#[inline(always)]
+fn current<Iter: Iterator>(iter: &Reiterator<Iter>) -> &Iter::Item {
+    iter.get()
+        .expect("Internal error: successful `step` left an iterator past its end")
+        .value
+}
+
+/// Move each iterator into position for the next combination with exactly this index sum,
+/// or `None` if there isn't one. Same `walk` as `BreadthFirstZipped::next_with`, recursing over a slice instead of nested types.
+#[inline]
+fn advance<Iter: Iterator>(
+    iters: &[Reiterator<Iter>],
+    base_case: &Cell<bool>,
+    index_sum: usize,
+) -> Option<()> {
+    let Some((head, tail)) = iters.split_first() else {
+        return (index_sum == 0 && base_case.replace(false)).then_some(());
+    };
+    walk(
+        index_sum,
+        1,
+        || Some(head.index.get()),
+        |remaining| {
+            advance(tail, base_case, remaining)?;
+            head.get().map(|_| ())
+        },
+        || head.next().map(|_| ()),
+        || rewind(tail, base_case),
+    )
+}
+
+/// Rewind each iterator back to its starting point.
+#[inline]
+fn rewind<Iter: Iterator>(iters: &[Reiterator<Iter>], base_case: &Cell<bool>) {
+    for iter in iters {
+        iter.restart();
+    }
+    base_case.set(true);
+}
@@ -0,0 +1,39 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Views over consecutive combinations of a breadth-first zip, e.g. to diff each against the last.
+
+use crate::{BreadthFirst, BreadthFirstManager, Flatten};
+
+impl<'item, Tail: BreadthFirst<'item>, Schedule: Iterator<Item = usize>>
+    BreadthFirstManager<'item, Tail, Schedule>
+{
+    /// Iterator over each pair of adjacent combinations, starting with the first two.
+    /// Combinations are tuples of references, so holding on to the previous one copies a handful of pointers, never an element.
+    #[inline]
+    pub fn adjacent_pairs(
+        &'item self,
+    ) -> impl Iterator<
+        Item = (
+            <Tail::Advance as Flatten>::Flattened,
+            <Tail::Advance as Flatten>::Flattened,
+        ),
+    > + 'item
+    where
+        <Tail::Advance as Flatten>::Flattened: Copy,
+    {
+        let mut previous = None;
+        ::core::iter::from_fn(move || {
+            let first = match previous {
+                Some(first) => first,
+                None => self.next()?,
+            };
+            let second = self.next()?;
+            previous = Some(second);
+            Some((first, second))
+        })
+    }
+}